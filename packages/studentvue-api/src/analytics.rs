@@ -0,0 +1,181 @@
+use crate::dates::parse_date;
+use crate::models::{Absence, AttendanceKind, AttendanceReport, CalendarEvent};
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttendanceSummary {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub school_days: usize,
+    pub days_absent: usize,
+    pub attendance_rate: f64,
+    pub missed_periods_by_course: BTreeMap<String, usize>,
+    pub tardies_by_course: BTreeMap<String, usize>,
+    pub streaks: Vec<AbsenceStreak>,
+    pub chronically_absent: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbsenceStreak {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub days: usize,
+}
+
+/// Computes attendance statistics from an [`AttendanceReport`] and the school
+/// calendar, either freshly fetched or loaded from a saved snapshot.
+///
+/// A day counts as absent when at least half of the day's periods were missed
+/// for a reason other than a tardy or school activity. Absences without period
+/// detail fall back to the classification of the whole-day reason.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttendanceAnalytics {
+    pub chronic_threshold: f64,
+}
+
+impl Default for AttendanceAnalytics {
+    fn default() -> Self {
+        Self {
+            chronic_threshold: 0.10,
+        }
+    }
+}
+
+impl AttendanceAnalytics {
+    pub fn new(chronic_threshold: f64) -> Self {
+        Self { chronic_threshold }
+    }
+
+    pub fn summarize(
+        &self,
+        report: &AttendanceReport,
+        calendar: &[CalendarEvent],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> AttendanceSummary {
+        let mut school_days = school_days(calendar, start, end);
+        let mut absent_days = BTreeSet::new();
+        let mut missed_periods_by_course = BTreeMap::new();
+        let mut tardies_by_course = BTreeMap::new();
+
+        for absence in &report.absences {
+            let Some(date) = parse_date(&absence.date) else {
+                continue;
+            };
+            if date < start || date > end {
+                continue;
+            }
+
+            for period in &absence.periods {
+                let counts = if is_tardy(period.kind) {
+                    &mut tardies_by_course
+                } else if is_missed(period.kind) {
+                    &mut missed_periods_by_course
+                } else {
+                    continue;
+                };
+                *counts.entry(period.course.clone()).or_insert(0) += 1;
+            }
+
+            if is_absent_day(absence, report.period_count) {
+                school_days.insert(date);
+                absent_days.insert(date);
+            }
+        }
+
+        let days_absent = absent_days.len();
+        let attendance_rate = if school_days.is_empty() {
+            1.0
+        } else {
+            1.0 - days_absent as f64 / school_days.len() as f64
+        };
+
+        AttendanceSummary {
+            start,
+            end,
+            school_days: school_days.len(),
+            days_absent,
+            attendance_rate,
+            missed_periods_by_course,
+            tardies_by_course,
+            streaks: streaks(&school_days, &absent_days),
+            chronically_absent: 1.0 - attendance_rate >= self.chronic_threshold,
+        }
+    }
+}
+
+/// Returns the weekdays between `start` and `end` (inclusive) that the calendar
+/// does not mark as a holiday or non-school day.
+pub fn school_days(
+    calendar: &[CalendarEvent],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> BTreeSet<NaiveDate> {
+    let closed: BTreeSet<NaiveDate> = calendar
+        .iter()
+        .filter(|event| event.is_school_closed())
+        .filter_map(|event| parse_date(&event.date))
+        .collect();
+
+    start
+        .iter_days()
+        .take_while(|day| *day <= end)
+        .filter(|day| !matches!(day.weekday(), Weekday::Sat | Weekday::Sun))
+        .filter(|day| !closed.contains(day))
+        .collect()
+}
+
+fn is_tardy(kind: AttendanceKind) -> bool {
+    matches!(kind, AttendanceKind::Tardy | AttendanceKind::UnexcusedTardy)
+}
+
+fn is_missed(kind: AttendanceKind) -> bool {
+    !is_tardy(kind) && kind != AttendanceKind::Activity
+}
+
+fn is_absent_day(absence: &Absence, period_count: u32) -> bool {
+    if absence.periods.is_empty() {
+        return is_missed(absence.kind);
+    }
+
+    let missed = absence.periods.iter().filter(|p| is_missed(p.kind)).count();
+    let total = if period_count > 0 {
+        period_count as usize
+    } else {
+        absence.periods.len()
+    };
+    missed > 0 && missed * 2 >= total
+}
+
+fn streaks(
+    school_days: &BTreeSet<NaiveDate>,
+    absent_days: &BTreeSet<NaiveDate>,
+) -> Vec<AbsenceStreak> {
+    let mut streaks = Vec::new();
+    let mut current: Option<AbsenceStreak> = None;
+
+    for day in school_days {
+        if absent_days.contains(day) {
+            match current {
+                Some(ref mut streak) => {
+                    streak.end = *day;
+                    streak.days += 1;
+                }
+                None => {
+                    current = Some(AbsenceStreak {
+                        start: *day,
+                        end: *day,
+                        days: 1,
+                    })
+                }
+            }
+        } else if let Some(streak) = current.take() {
+            streaks.push(streak);
+        }
+    }
+    streaks.extend(current);
+
+    streaks
+}
@@ -0,0 +1,368 @@
+use crate::document::{
+    create_new_file, detect_file_mime, file_name_for, sanitize_file_name, StreamedDocument,
+};
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Write};
+use std::path::{Path, PathBuf};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+pub const DEFAULT_ARCHIVE_CONCURRENCY: usize = 4;
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveItemKind {
+    Document,
+    ReportCard,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub document_gu: String,
+    pub kind: ArchiveItemKind,
+    pub document_type: String,
+    pub date: String,
+    pub comment: String,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl ArchiveManifest {
+    pub fn contains(&self, kind: ArchiveItemKind, document_gu: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.kind == kind && entry.document_gu == document_gu)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchiveFailure {
+    pub document_gu: String,
+    pub kind: ArchiveItemKind,
+    pub error: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchiveReport {
+    pub path: PathBuf,
+    pub downloaded: usize,
+    pub skipped: usize,
+    pub failed: Vec<ArchiveFailure>,
+}
+
+/// A listed document or report card waiting to be downloaded.
+#[derive(Debug, Clone)]
+pub(crate) struct ArchiveItem {
+    pub kind: ArchiveItemKind,
+    pub document_gu: String,
+    pub document_type: String,
+    pub date: String,
+    pub comment: String,
+}
+
+/// Where archived files are written: a plain directory, or a ZIP file when the
+/// destination ends in `.zip`.
+///
+/// A directory archive rewrites its manifest after every file, so an
+/// interrupted run can be resumed. A ZIP archive is built next to the
+/// destination and only replaces it once complete, carrying over everything
+/// from the previous archive; if anything fails, the partial file is removed.
+///
+/// Each download is streamed into a staging file first (see
+/// [`ArchiveSink::staging_path`]) and then copied into place, so no document
+/// is ever held in memory in full. File and ZIP I/O is blocking, so the async
+/// methods run it on Tokio's blocking thread pool.
+pub(crate) enum ArchiveSink {
+    Directory {
+        dir: PathBuf,
+        manifest: ArchiveManifest,
+    },
+    Zip {
+        path: PathBuf,
+        partial: PathBuf,
+        writer: Box<ZipWriter<File>>,
+        names: HashSet<String>,
+        manifest: ArchiveManifest,
+    },
+}
+
+impl ArchiveSink {
+    pub(crate) async fn open(dest: PathBuf) -> Result<Self> {
+        run_blocking(move || Self::open_blocking(&dest)).await
+    }
+
+    /// Moves a download from its staging file into the archive. The staging
+    /// file is removed whether or not this succeeds.
+    pub(crate) async fn add(
+        mut self,
+        item: ArchiveItem,
+        document: StreamedDocument,
+        staged: PathBuf,
+    ) -> Result<Self> {
+        run_blocking(move || {
+            let result = self.add_blocking(&item, &document, &staged);
+            let _ = fs::remove_file(&staged);
+            self.discard_on_error(result)?;
+            Ok(self)
+        })
+        .await
+    }
+
+    pub(crate) async fn finish(self) -> Result<PathBuf> {
+        run_blocking(move || self.finish_blocking()).await
+    }
+
+    fn open_blocking(dest: &Path) -> Result<Self> {
+        let is_zip = dest
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"));
+
+        if is_zip {
+            Self::open_zip(dest)
+        } else {
+            Self::open_directory(dest)
+        }
+    }
+
+    fn open_directory(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+
+        let manifest_path = dir.join(MANIFEST_FILE_NAME);
+        let mut manifest: ArchiveManifest = if manifest_path.exists() {
+            serde_json::from_slice(&fs::read(&manifest_path)?)?
+        } else {
+            ArchiveManifest::default()
+        };
+        manifest
+            .entries
+            .retain(|entry| dir.join(&entry.file_name).is_file());
+
+        Ok(Self::Directory {
+            dir: dir.to_path_buf(),
+            manifest,
+        })
+    }
+
+    fn open_zip(path: &Path) -> Result<Self> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(".partial");
+        let partial = PathBuf::from(partial);
+
+        let writer = Box::new(ZipWriter::new(File::create(&partial)?));
+        let result = Self::copy_existing(path, writer);
+        let (writer, names, manifest) = discard_partial(&partial, result)?;
+
+        Ok(Self::Zip {
+            path: path.to_path_buf(),
+            partial,
+            writer,
+            names,
+            manifest,
+        })
+    }
+
+    /// Carries the files of an existing archive over into `writer`.
+    fn copy_existing(
+        path: &Path,
+        mut writer: Box<ZipWriter<File>>,
+    ) -> Result<(Box<ZipWriter<File>>, HashSet<String>, ArchiveManifest)> {
+        let mut names = HashSet::new();
+        let mut manifest = ArchiveManifest::default();
+
+        if path.exists() {
+            let mut existing = ZipArchive::new(BufReader::new(File::open(path)?))?;
+            for i in 0..existing.len() {
+                let file = existing.by_index_raw(i)?;
+                let name = file.name().to_string();
+                if name == MANIFEST_FILE_NAME {
+                    continue;
+                }
+                writer.raw_copy_file(file)?;
+                names.insert(name);
+            }
+
+            if let Ok(file) = existing.by_name(MANIFEST_FILE_NAME) {
+                manifest = serde_json::from_reader(file)?;
+            }
+            manifest
+                .entries
+                .retain(|entry| names.contains(&entry.file_name));
+        }
+
+        Ok((writer, names, manifest))
+    }
+
+    /// Where `item` is downloaded to before [`ArchiveSink::add`]: a hidden
+    /// file in the destination directory, or next to the ZIP file.
+    pub(crate) fn staging_path(&self, item: &ArchiveItem) -> PathBuf {
+        let kind = match item.kind {
+            ArchiveItemKind::Document => "document",
+            ArchiveItemKind::ReportCard => "report-card",
+        };
+        let name = sanitize_file_name(&format!("{kind}-{}.download", item.document_gu));
+        match self {
+            Self::Directory { dir, .. } => dir.join(format!(".{name}")),
+            Self::Zip { partial, .. } => {
+                let mut staged = partial.as_os_str().to_owned();
+                staged.push(format!("-{name}"));
+                PathBuf::from(staged)
+            }
+        }
+    }
+
+    pub(crate) fn manifest(&self) -> &ArchiveManifest {
+        match self {
+            Self::Directory { manifest, .. } | Self::Zip { manifest, .. } => manifest,
+        }
+    }
+
+    fn add_blocking(
+        &mut self,
+        item: &ArchiveItem,
+        document: &StreamedDocument,
+        staged: &Path,
+    ) -> Result<()> {
+        let mime_type = detect_file_mime(staged, &document.file_name)?;
+        let document_name = file_name_for(&document.file_name, mime_type);
+        let entry = |file_name: String| ManifestEntry {
+            document_gu: item.document_gu.clone(),
+            kind: item.kind,
+            document_type: item.document_type.clone(),
+            date: item.date.clone(),
+            comment: item.comment.clone(),
+            file_name,
+            mime_type: mime_type.to_string(),
+            size: document.bytes_written,
+        };
+
+        match self {
+            Self::Directory { dir, manifest } => {
+                let file_name = match document_name {
+                    name if name == MANIFEST_FILE_NAME => format!("document-{name}"),
+                    name => name,
+                };
+                let (path, mut file) = create_new_file(dir, &file_name)?;
+                io::copy(&mut File::open(staged)?, &mut file)?;
+                let file_name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                manifest.entries.push(entry(file_name));
+                write_manifest(&dir.join(MANIFEST_FILE_NAME), manifest)
+            }
+            Self::Zip {
+                writer,
+                names,
+                manifest,
+                ..
+            } => {
+                let file_name = unique_name(names, &document_name);
+                writer.start_file(file_name.as_str(), zip_options())?;
+                io::copy(&mut File::open(staged)?, writer)?;
+                manifest.entries.push(entry(file_name));
+                Ok(())
+            }
+        }
+    }
+
+    fn discard_on_error(&self, result: Result<()>) -> Result<()> {
+        match self {
+            Self::Zip { partial, .. } => discard_partial(partial, result),
+            Self::Directory { .. } => result,
+        }
+    }
+
+    fn finish_blocking(self) -> Result<PathBuf> {
+        match self {
+            Self::Directory { dir, manifest } => {
+                write_manifest(&dir.join(MANIFEST_FILE_NAME), &manifest)?;
+                Ok(dir)
+            }
+            Self::Zip {
+                path,
+                partial,
+                mut writer,
+                manifest,
+                ..
+            } => {
+                let result = (|| {
+                    writer.start_file(MANIFEST_FILE_NAME, zip_options())?;
+                    writer.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+                    writer.finish()?;
+                    fs::rename(&partial, &path)?;
+                    Ok(())
+                })();
+                discard_partial(&partial, result)?;
+                Ok(path)
+            }
+        }
+    }
+}
+
+async fn run_blocking<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(io::Error::from)?
+}
+
+/// Removes the partial ZIP file when `result` is an error.
+fn discard_partial<T>(partial: &Path, result: Result<T>) -> Result<T> {
+    if result.is_err() {
+        let _ = fs::remove_file(partial);
+    }
+    result
+}
+
+fn zip_options() -> FileOptions {
+    FileOptions::default().compression_method(CompressionMethod::Deflated)
+}
+
+fn write_manifest(path: &Path, manifest: &ArchiveManifest) -> Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    fs::write(&temp, serde_json::to_vec_pretty(manifest)?)?;
+    fs::rename(&temp, path)?;
+    Ok(())
+}
+
+fn unique_name(names: &mut HashSet<String>, file_name: &str) -> String {
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (file_name, None),
+    };
+
+    let mut attempt = 0;
+    loop {
+        let candidate = match (attempt, extension) {
+            (0, _) => file_name.to_string(),
+            (n, Some(extension)) => format!("{stem} ({n}).{extension}"),
+            (n, None) => format!("{stem} ({n})"),
+        };
+        if candidate != MANIFEST_FILE_NAME && names.insert(candidate.clone()) {
+            return candidate;
+        }
+        attempt += 1;
+    }
+}
@@ -0,0 +1,136 @@
+use crate::models::{Assignment, Course, Mark};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AssignmentStatus {
+    Missing,
+    NotTurnedIn,
+    Zero,
+    Upcoming,
+}
+
+/// A gradebook assignment together with the course and mark it belongs to.
+#[derive(Debug, Clone)]
+pub struct TrackedAssignment<'a> {
+    pub course: &'a Course,
+    pub mark: &'a Mark,
+    pub assignment: &'a Assignment,
+    pub due: Option<NaiveDate>,
+    pub status: AssignmentStatus,
+}
+
+impl TrackedAssignment<'_> {
+    pub fn teacher(&self) -> &str {
+        &self.course.staff
+    }
+
+    pub fn teacher_email(&self) -> &str {
+        &self.course.staff_email
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AssignmentOverview<'a> {
+    pub missing: Vec<TrackedAssignment<'a>>,
+    pub upcoming: Vec<TrackedAssignment<'a>>,
+}
+
+/// Collects missing work and work due within `days` of `today` across all
+/// courses, each list sorted by due date.
+///
+/// An assignment is missing when its score or notes say it is missing or not
+/// turned in, or when it is graded as zero points. It is upcoming when it is
+/// ungraded and due between `today` and `today + days` inclusive. Assignments
+/// repeated under several marks (such as a quarter and its semester) are only
+/// listed once.
+pub fn assignment_overview<'a>(
+    courses: &'a [Course],
+    today: NaiveDate,
+    days: i64,
+) -> AssignmentOverview<'a> {
+    let horizon = Duration::try_days(days)
+        .and_then(|days| today.checked_add_signed(days))
+        .unwrap_or(if days < 0 {
+            NaiveDate::MIN
+        } else {
+            NaiveDate::MAX
+        });
+    let mut overview = AssignmentOverview::default();
+    let mut seen = HashSet::new();
+
+    for course in courses {
+        for mark in &course.marks {
+            for assignment in &mark.assignments {
+                let key = if assignment.gradebook_id.is_empty() {
+                    format!(
+                        "{}|{}|{}",
+                        course.title, assignment.measure, assignment.due_date
+                    )
+                } else {
+                    assignment.gradebook_id.clone()
+                };
+                if !seen.insert(key) {
+                    continue;
+                }
+
+                let due = assignment.due();
+                let tracked = |status| TrackedAssignment {
+                    course,
+                    mark,
+                    assignment,
+                    due,
+                    status,
+                };
+
+                if let Some(status) = missing_status(assignment) {
+                    overview.missing.push(tracked(status));
+                } else if !assignment.is_graded()
+                    && due.is_some_and(|due| due >= today && due <= horizon)
+                {
+                    overview.upcoming.push(tracked(AssignmentStatus::Upcoming));
+                }
+            }
+        }
+    }
+
+    sort_by_due(&mut overview.missing);
+    sort_by_due(&mut overview.upcoming);
+    overview
+}
+
+/// Classifies an assignment as missing, not turned in or zero, if it is any
+/// of those.
+pub fn missing_status(assignment: &Assignment) -> Option<AssignmentStatus> {
+    let text = format!("{} {}", assignment.score, assignment.notes).to_lowercase();
+    if text.contains("not turned in") {
+        return Some(AssignmentStatus::NotTurnedIn);
+    }
+    if text.contains("missing") {
+        return Some(AssignmentStatus::Missing);
+    }
+
+    let zero = assignment.points_earned() == Some(0.0)
+        && assignment
+            .points_possible()
+            .is_some_and(|possible| possible > 0.0);
+    zero.then_some(AssignmentStatus::Zero)
+}
+
+fn sort_by_due(assignments: &mut [TrackedAssignment]) {
+    assignments.sort_by(|a, b| {
+        (
+            a.due.is_none(),
+            a.due,
+            &a.course.title,
+            &a.assignment.measure,
+        )
+            .cmp(&(
+                b.due.is_none(),
+                b.due,
+                &b.course.title,
+                &b.assignment.measure,
+            ))
+    });
+}
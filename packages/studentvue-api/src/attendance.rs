@@ -0,0 +1,59 @@
+use crate::models::AttendanceKind;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Maps district-defined attendance reason codes to an [`AttendanceKind`].
+///
+/// Codes are matched case-insensitively after trimming whitespace. The default
+/// table covers the reason names most Synergy districts ship with; districts
+/// with custom codes can extend or replace it with [`AttendanceCodes::insert`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttendanceCodes {
+    codes: HashMap<String, AttendanceKind>,
+}
+
+impl AttendanceCodes {
+    pub fn empty() -> Self {
+        Self {
+            codes: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, code: &str, kind: AttendanceKind) -> &mut Self {
+        self.codes.insert(Self::normalize(code), kind);
+        self
+    }
+
+    pub fn with(mut self, code: &str, kind: AttendanceKind) -> Self {
+        self.insert(code, kind);
+        self
+    }
+
+    pub fn classify(&self, code: &str) -> AttendanceKind {
+        self.codes
+            .get(&Self::normalize(code))
+            .copied()
+            .unwrap_or(AttendanceKind::Unknown)
+    }
+
+    fn normalize(code: &str) -> String {
+        code.trim().to_lowercase()
+    }
+}
+
+impl Default for AttendanceCodes {
+    fn default() -> Self {
+        Self::empty()
+            .with("Excused", AttendanceKind::Excused)
+            .with("Illness", AttendanceKind::Excused)
+            .with("Medical", AttendanceKind::Excused)
+            .with("Unexcused", AttendanceKind::Unexcused)
+            .with("Truant", AttendanceKind::Unexcused)
+            .with("Tardy", AttendanceKind::Tardy)
+            .with("Excused Tardy", AttendanceKind::Tardy)
+            .with("Unexcused Tardy", AttendanceKind::UnexcusedTardy)
+            .with("Activity", AttendanceKind::Activity)
+            .with("School Activity", AttendanceKind::Activity)
+            .with("Field Trip", AttendanceKind::Activity)
+    }
+}
@@ -0,0 +1,40 @@
+use crate::models::{Assignment, CalendarEvent, Course};
+use std::collections::HashMap;
+
+/// An assignment calendar event matched to its gradebook assignment.
+#[derive(Debug, Clone)]
+pub struct LinkedAssignment<'a> {
+    pub event: &'a CalendarEvent,
+    pub course: &'a Course,
+    pub assignment: &'a Assignment,
+}
+
+/// Matches assignment events to gradebook assignments whose `GradebookID`
+/// equals the event's `AGU`. Events without a match are skipped.
+pub fn link_assignments<'a>(
+    events: &'a [CalendarEvent],
+    courses: &'a [Course],
+) -> Vec<LinkedAssignment<'a>> {
+    let mut by_id = HashMap::new();
+    for course in courses {
+        for mark in &course.marks {
+            for assignment in &mark.assignments {
+                if !assignment.gradebook_id.is_empty() {
+                    by_id.insert(assignment.gradebook_id.as_str(), (course, assignment));
+                }
+            }
+        }
+    }
+
+    events
+        .iter()
+        .filter_map(|event| {
+            let (course, assignment) = by_id.get(event.assignment_gu()?)?;
+            Some(LinkedAssignment {
+                event,
+                course,
+                assignment,
+            })
+        })
+        .collect()
+}
@@ -1,16 +1,42 @@
+use crate::analytics::{AttendanceAnalytics, AttendanceSummary};
+use crate::archive::{
+    ArchiveFailure, ArchiveItem, ArchiveItemKind, ArchiveReport, ArchiveSink,
+    DEFAULT_ARCHIVE_CONCURRENCY,
+};
+use crate::attendance::AttendanceCodes;
+use crate::dates::{format_date, parse_date};
+use crate::document::{doc_type_for, Base64Extractor, StreamedDocument};
 use crate::error::{Error, Result};
 use crate::models::*;
+use crate::snapshot::{
+    Snapshot, SnapshotBuilder, SnapshotPart, SnapshotRequest, DEFAULT_SNAPSHOT_CONCURRENCY,
+};
 use crate::soap::SoapClient;
-use quick_xml::events::Event;
+use crate::student::Student;
+use crate::trends::{GradeTrendAnalysis, GradeTrends};
+use chrono::{Datelike, Local, Months, NaiveDate};
+use futures::stream::{self, FuturesUnordered, StreamExt, TryStreamExt};
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
-use reqwest::Client;
-use std::collections::HashMap;
+use reqwest::{Client, Response};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWrite;
+
+const PROCESS_REQUEST: &str = "http://edupoint.com/webservices/ProcessWebServiceRequest";
+const PROCESS_REQUEST_MULTI_WEB: &str =
+    "http://edupoint.com/webservices/ProcessWebServiceRequestMultiWeb";
+
+/// How many requests a single call that fans out, such as fetching a
+/// calendar range month by month, keeps in flight at once.
+const FAN_OUT_CONCURRENCY: usize = 4;
 
 pub struct StudentVueClient {
     client: Client,
     base_url: String,
     username: String,
     password: String,
+    attendance_codes: AttendanceCodes,
 }
 
 impl StudentVueClient {
@@ -26,9 +52,15 @@ impl StudentVueClient {
             base_url,
             username,
             password,
+            attendance_codes: AttendanceCodes::default(),
         }
     }
 
+    pub fn with_attendance_codes(mut self, attendance_codes: AttendanceCodes) -> Self {
+        self.attendance_codes = attendance_codes;
+        self
+    }
+
     async fn make_request(
         &self,
         service_handle: &str,
@@ -37,12 +69,52 @@ impl StudentVueClient {
         multi_web: bool,
     ) -> Result<String> {
         let param_str = SoapClient::build_params(params);
+        self.make_raw_request(
+            service_handle,
+            method_name,
+            &param_str,
+            multi_web,
+            PROCESS_REQUEST,
+        )
+        .await
+    }
+
+    async fn make_raw_request(
+        &self,
+        service_handle: &str,
+        method_name: &str,
+        param_str: &str,
+        multi_web: bool,
+        soap_action: &str,
+    ) -> Result<String> {
+        let response = self
+            .send_request(
+                service_handle,
+                method_name,
+                param_str,
+                multi_web,
+                soap_action,
+            )
+            .await?;
+
+        let response_text = response.text().await?;
+        SoapClient::parse_response(&response_text)
+    }
+
+    async fn send_request(
+        &self,
+        service_handle: &str,
+        method_name: &str,
+        param_str: &str,
+        multi_web: bool,
+        soap_action: &str,
+    ) -> Result<Response> {
         let soap_request = SoapClient::create_request(
             &self.username,
             &self.password,
             service_handle,
             method_name,
-            &param_str,
+            param_str,
             multi_web,
         );
 
@@ -56,10 +128,7 @@ impl StudentVueClient {
             .client
             .post(&endpoint)
             .header("Content-Type", "text/xml; charset=utf-8")
-            .header(
-                "SOAPAction",
-                "http://edupoint.com/webservices/ProcessWebServiceRequest",
-            )
+            .header("SOAPAction", soap_action)
             .body(soap_request)
             .send()
             .await?;
@@ -71,8 +140,33 @@ impl StudentVueClient {
             )));
         }
 
-        let response_text = response.text().await?;
-        SoapClient::parse_response(&response_text)
+        Ok(response)
+    }
+
+    async fn stream_document<W: AsyncWrite + Unpin>(
+        &self,
+        method_name: &str,
+        document_gu: &str,
+        writer: W,
+    ) -> Result<StreamedDocument> {
+        let mut params = HashMap::new();
+        params.insert("DocumentGU".to_string(), document_gu.to_string());
+
+        let mut response = self
+            .send_request(
+                "PXPWebServices",
+                method_name,
+                &SoapClient::build_params(&params),
+                false,
+                PROCESS_REQUEST,
+            )
+            .await?;
+
+        let mut extractor = Base64Extractor::new(writer);
+        while let Some(chunk) = response.chunk().await? {
+            extractor.push(&chunk).await?;
+        }
+        extractor.finish(document_gu).await
     }
 
     pub async fn get_districts_by_zip(&self, zip_code: &str) -> Result<Vec<DistrictInfo>> {
@@ -122,6 +216,10 @@ impl StudentVueClient {
     }
 
     pub async fn get_calendar(&self, date: &str) -> Result<Vec<CalendarEvent>> {
+        Ok(self.get_calendar_listing(date).await?.events)
+    }
+
+    pub async fn get_calendar_listing(&self, date: &str) -> Result<CalendarListing> {
         let mut params = HashMap::new();
         params.insert("childIntID".to_string(), "0".to_string());
         params.insert("RequestDate".to_string(), date.to_string());
@@ -134,6 +232,10 @@ impl StudentVueClient {
     }
 
     pub async fn get_attendance(&self) -> Result<Vec<Absence>> {
+        Ok(self.get_attendance_report().await?.absences)
+    }
+
+    pub async fn get_attendance_report(&self) -> Result<AttendanceReport> {
         let mut params = HashMap::new();
         params.insert("ChildIntID".to_string(), "0".to_string());
 
@@ -144,7 +246,90 @@ impl StudentVueClient {
         self.parse_attendance(&xml_data)
     }
 
+    pub async fn get_attendance_summary(
+        &self,
+        analytics: &AttendanceAnalytics,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<AttendanceSummary> {
+        let report = self.get_attendance_report().await?;
+
+        let calendar = self.get_calendar_range(start, end).await?;
+
+        let start = calendar
+            .school_begin_date
+            .map_or(start, |begin| start.max(begin));
+        let end = calendar
+            .school_end_date
+            .map_or(end, |school_end| end.min(school_end));
+
+        Ok(analytics.summarize(&report, &calendar.events, start, end))
+    }
+
+    pub async fn get_calendar_range(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<CalendarRange> {
+        let mut months = Vec::new();
+        let mut month = start.with_day(1).unwrap_or(start);
+        while month <= end {
+            months.push(format_date(month));
+            month = match month.checked_add_months(Months::new(1)) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        let listings: Vec<CalendarListing> = stream::iter(&months)
+            .map(|month| self.get_calendar_listing(month))
+            .buffered(FAN_OUT_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let mut range = CalendarRange {
+            start,
+            end,
+            school_begin_date: None,
+            school_end_date: None,
+            events: Vec::new(),
+        };
+        let mut seen = HashSet::new();
+
+        for listing in listings {
+            if range.school_begin_date.is_none() {
+                range.school_begin_date = parse_date(&listing.school_begin_date);
+            }
+            if range.school_end_date.is_none() {
+                range.school_end_date = parse_date(&listing.school_end_date);
+            }
+
+            for event in listing.events {
+                let in_range =
+                    parse_date(&event.date).map_or(true, |date| date >= start && date <= end);
+                let key = (
+                    event.date.clone(),
+                    event.title.clone(),
+                    event.day_type.clone(),
+                );
+                if in_range && seen.insert(key) {
+                    range.events.push(event);
+                }
+            }
+        }
+
+        range
+            .events
+            .sort_by_key(|event| parse_date(&event.date).unwrap_or(NaiveDate::MAX));
+
+        Ok(range)
+    }
+
     pub async fn get_gradebook(&self, report_period: Option<usize>) -> Result<Vec<Course>> {
+        Ok(self.get_gradebook_listing(report_period).await?.courses)
+    }
+
+    pub async fn get_gradebook_listing(&self, report_period: Option<usize>) -> Result<Gradebook> {
         let mut params = HashMap::new();
         params.insert("ChildIntID".to_string(), "0".to_string());
         if let Some(rp) = report_period {
@@ -158,6 +343,70 @@ impl StudentVueClient {
         self.parse_gradebook(&xml_data)
     }
 
+    pub async fn get_reporting_periods(&self) -> Result<Vec<ReportPeriod>> {
+        Ok(self.get_gradebook_listing(None).await?.reporting_periods)
+    }
+
+    /// The reporting period whose dates include `date`, if any.
+    pub async fn reporting_period_for(&self, date: NaiveDate) -> Result<Option<ReportPeriod>> {
+        Ok(self
+            .get_gradebook_listing(None)
+            .await?
+            .reporting_period_for(date)
+            .cloned())
+    }
+
+    /// The reporting period in effect today, falling back to the most recently
+    /// ended period during breaks. See [`Gradebook::reporting_period_on`].
+    pub async fn current_reporting_period(&self) -> Result<Option<ReportPeriod>> {
+        let today = Local::now().date_naive();
+        Ok(self
+            .get_gradebook_listing(None)
+            .await?
+            .reporting_period_on(today)
+            .cloned())
+    }
+
+    /// Fetches the gradebook for the current reporting period, whichever
+    /// period the portal shows by default.
+    pub async fn get_current_gradebook(&self) -> Result<Gradebook> {
+        let today = Local::now().date_naive();
+        let gradebook = self.get_gradebook_listing(None).await?;
+        let current = gradebook
+            .reporting_period_on(today)
+            .and_then(|period| period.index);
+
+        match current {
+            Some(index) if !gradebook.shows_period(index) => {
+                self.get_gradebook_listing(Some(index)).await
+            }
+            _ => Ok(gradebook),
+        }
+    }
+
+    /// Fetches the gradebook for every reporting period, in period order.
+    pub async fn get_all_gradebooks(&self) -> Result<Vec<Gradebook>> {
+        let current = self.get_gradebook_listing(None).await?;
+        let indexes: Vec<usize> = current
+            .reporting_periods
+            .iter()
+            .filter_map(|period| period.index)
+            .collect();
+        if indexes.is_empty() {
+            return Ok(vec![current]);
+        }
+
+        stream::iter(indexes)
+            .map(|index| self.get_gradebook_listing(Some(index)))
+            .buffered(FAN_OUT_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    pub async fn get_grade_trends(&self, analysis: &GradeTrendAnalysis) -> Result<GradeTrends> {
+        Ok(analysis.analyze(&self.get_all_gradebooks().await?))
+    }
+
     pub async fn get_class_notes(&self) -> Result<String> {
         let mut params = HashMap::new();
         params.insert("childIntID".to_string(), "0".to_string());
@@ -166,6 +415,17 @@ impl StudentVueClient {
             .await
     }
 
+    pub async fn get_course_history(&self) -> Result<CourseHistory> {
+        let mut params = HashMap::new();
+        params.insert("childIntID".to_string(), "0".to_string());
+
+        let xml_data = self
+            .make_request("PXPWebServices", "CourseHistory", &params, false)
+            .await?;
+
+        self.parse_course_history(&xml_data)
+    }
+
     pub async fn get_student_info(&self) -> Result<StudentInfo> {
         let mut params = HashMap::new();
         params.insert("ChildIntID".to_string(), "0".to_string());
@@ -205,6 +465,94 @@ impl StudentVueClient {
         self.parse_school_info(&xml_data)
     }
 
+    /// Fetches the student's info, school, current gradebook, schedule and
+    /// attendance together and links them course by course.
+    pub async fn get_student(&self) -> Result<Student> {
+        let (info, school, courses, schedule, attendance) = futures::try_join!(
+            self.get_student_info(),
+            self.get_school_info(),
+            self.get_gradebook(None),
+            self.get_class_schedule(None),
+            self.get_attendance_report(),
+        )?;
+
+        Ok(Student::link(info, school, courses, schedule, attendance))
+    }
+
+    pub async fn snapshot(&self) -> Result<Snapshot> {
+        self.snapshot_with_concurrency(DEFAULT_SNAPSHOT_CONCURRENCY)
+            .await
+    }
+
+    /// Fetches every endpoint, including the gradebook for each reporting
+    /// period and this month's calendar, with at most `concurrency` requests
+    /// in flight. Endpoints that fail are recorded in [`Snapshot::errors`]
+    /// instead of failing the whole snapshot.
+    pub async fn snapshot_with_concurrency(&self, concurrency: usize) -> Result<Snapshot> {
+        let now = Local::now();
+        let month = format_date(now.date_naive().with_day(1).unwrap_or(now.date_naive()));
+        let mut builder = SnapshotBuilder::new(now);
+        let mut queue: VecDeque<SnapshotRequest> = SnapshotRequest::INITIAL.into_iter().collect();
+        let mut running = FuturesUnordered::new();
+
+        loop {
+            while running.len() < concurrency.max(1) {
+                let Some(request) = queue.pop_front() else {
+                    break;
+                };
+                running.push(self.fetch_snapshot_part(request, &month));
+            }
+            let Some((request, result)) = running.next().await else {
+                break;
+            };
+            queue.extend(builder.add(request, result));
+        }
+
+        Ok(builder.finish())
+    }
+
+    async fn fetch_snapshot_part(
+        &self,
+        request: SnapshotRequest,
+        month: &str,
+    ) -> (SnapshotRequest, Result<SnapshotPart>) {
+        let result = match request {
+            SnapshotRequest::StudentInfo => {
+                self.get_student_info().await.map(SnapshotPart::StudentInfo)
+            }
+            SnapshotRequest::SchoolInfo => {
+                self.get_school_info().await.map(SnapshotPart::SchoolInfo)
+            }
+            SnapshotRequest::Schedule => self
+                .get_class_schedule(None)
+                .await
+                .map(SnapshotPart::Schedule),
+            SnapshotRequest::Gradebook(index) => self
+                .get_gradebook_listing(index)
+                .await
+                .map(SnapshotPart::Gradebook),
+            SnapshotRequest::Attendance => self
+                .get_attendance_report()
+                .await
+                .map(SnapshotPart::Attendance),
+            SnapshotRequest::Messages => self.get_messages().await.map(SnapshotPart::Messages),
+            SnapshotRequest::Calendar => self
+                .get_calendar_listing(month)
+                .await
+                .map(SnapshotPart::Calendar),
+            SnapshotRequest::Documents => self.list_documents().await.map(SnapshotPart::Documents),
+            SnapshotRequest::ReportCards => self
+                .list_report_cards()
+                .await
+                .map(SnapshotPart::ReportCards),
+            SnapshotRequest::Health => self
+                .get_student_health_info(true, true, true)
+                .await
+                .map(SnapshotPart::Health),
+        };
+        (request, result)
+    }
+
     pub async fn list_report_cards(&self) -> Result<Vec<ReportCard>> {
         let mut params = HashMap::new();
         params.insert("childIntID".to_string(), "0".to_string());
@@ -232,6 +580,15 @@ impl StudentVueClient {
         self.parse_document_data(&xml_data)
     }
 
+    pub async fn download_report_card<W: AsyncWrite + Unpin>(
+        &self,
+        document_gu: &str,
+        writer: W,
+    ) -> Result<StreamedDocument> {
+        self.stream_document("GetReportCardDocumentData", document_gu, writer)
+            .await
+    }
+
     pub async fn list_documents(&self) -> Result<Vec<Document>> {
         let mut params = HashMap::new();
         params.insert("childIntID".to_string(), "0".to_string());
@@ -259,6 +616,172 @@ impl StudentVueClient {
         self.parse_document_data(&xml_data)
     }
 
+    pub async fn download_document<W: AsyncWrite + Unpin>(
+        &self,
+        document_gu: &str,
+        writer: W,
+    ) -> Result<StreamedDocument> {
+        self.stream_document("GetContentOfAttachedDoc", document_gu, writer)
+            .await
+    }
+
+    pub async fn archive_all_documents(&self, dest: impl AsRef<Path>) -> Result<ArchiveReport> {
+        self.archive_all_documents_with_concurrency(dest, DEFAULT_ARCHIVE_CONCURRENCY)
+            .await
+    }
+
+    /// Downloads every document and report card into `dest`, a directory or a
+    /// `.zip` file, alongside a JSON manifest. Items already recorded in an
+    /// existing archive are skipped, and individual download failures are
+    /// reported rather than aborting the whole export.
+    pub async fn archive_all_documents_with_concurrency(
+        &self,
+        dest: impl AsRef<Path>,
+        concurrency: usize,
+    ) -> Result<ArchiveReport> {
+        let (documents, report_cards) =
+            futures::try_join!(self.list_documents(), self.list_report_cards())?;
+
+        let items = documents
+            .into_iter()
+            .map(|document| ArchiveItem {
+                kind: ArchiveItemKind::Document,
+                document_gu: document.document_gu,
+                document_type: document.document_type,
+                date: document.date,
+                comment: document.comment,
+            })
+            .chain(report_cards.into_iter().map(|report_card| ArchiveItem {
+                kind: ArchiveItemKind::ReportCard,
+                document_gu: report_card.document_gu,
+                document_type: "Report Card".to_string(),
+                date: report_card.end_date,
+                comment: if report_card.message.is_empty() {
+                    report_card.reporting_period
+                } else {
+                    format!("{}: {}", report_card.reporting_period, report_card.message)
+                },
+            }));
+
+        let mut sink = ArchiveSink::open(dest.as_ref().to_path_buf()).await?;
+        let (skipped, pending): (Vec<ArchiveItem>, Vec<ArchiveItem>) =
+            items.partition(|item| sink.manifest().contains(item.kind, &item.document_gu));
+
+        let pending: Vec<(ArchiveItem, PathBuf)> = pending
+            .into_iter()
+            .map(|item| {
+                let staged = sink.staging_path(&item);
+                (item, staged)
+            })
+            .collect();
+        let staged_paths: Vec<PathBuf> = pending.iter().map(|(_, staged)| staged.clone()).collect();
+
+        let mut downloads = stream::iter(pending)
+            .map(|(item, staged)| async move {
+                let document = self.download_archive_item(&item, &staged).await;
+                (item, staged, document)
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        let mut downloaded = 0;
+        let mut failed = Vec::new();
+        while let Some((item, staged, document)) = downloads.next().await {
+            match document {
+                Ok(document) => match sink.add(item, document, staged).await {
+                    Ok(next) => {
+                        sink = next;
+                        downloaded += 1;
+                    }
+                    Err(e) => {
+                        drop(downloads);
+                        for staged in &staged_paths {
+                            let _ = tokio::fs::remove_file(staged).await;
+                        }
+                        return Err(e);
+                    }
+                },
+                Err(e) => {
+                    let _ = tokio::fs::remove_file(&staged).await;
+                    failed.push(ArchiveFailure {
+                        document_gu: item.document_gu,
+                        kind: item.kind,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(ArchiveReport {
+            path: sink.finish().await?,
+            downloaded,
+            skipped: skipped.len(),
+            failed,
+        })
+    }
+
+    /// Streams one archive item into its staging file.
+    async fn download_archive_item(
+        &self,
+        item: &ArchiveItem,
+        staged: &Path,
+    ) -> Result<StreamedDocument> {
+        let file = tokio::fs::File::create(staged).await?;
+        match item.kind {
+            ArchiveItemKind::Document => self.download_document(&item.document_gu, file).await,
+            ArchiveItemKind::ReportCard => self.download_report_card(&item.document_gu, file).await,
+        }
+    }
+
+    /// Downloads a file a teacher attached to an assignment. Link resources
+    /// have nothing to download; open their `url` instead.
+    pub async fn get_assignment_resource(
+        &self,
+        resource: &AssignmentResource,
+    ) -> Result<DocumentData> {
+        Self::check_file_resource(resource)?;
+
+        let mut data = self.get_document(&resource.resource_id).await?;
+        if data.file_name.is_empty() {
+            data.file_name = resource.file_name.clone();
+        }
+        if data.document_gu.is_empty() {
+            data.document_gu = resource.resource_id.clone();
+        }
+        Ok(data)
+    }
+
+    pub async fn download_assignment_resource<W: AsyncWrite + Unpin>(
+        &self,
+        resource: &AssignmentResource,
+        writer: W,
+    ) -> Result<StreamedDocument> {
+        Self::check_file_resource(resource)?;
+
+        let mut streamed = self
+            .download_document(&resource.resource_id, writer)
+            .await?;
+        if streamed.file_name.is_empty() {
+            streamed.file_name = resource.file_name.clone();
+        }
+        Ok(streamed)
+    }
+
+    fn check_file_resource(resource: &AssignmentResource) -> Result<()> {
+        if !resource.is_file() {
+            return Err(Error::InvalidRequest(format!(
+                "Resource \"{}\" is a link, not a file",
+                resource.resource_name
+            )));
+        }
+        if resource.resource_id.is_empty() {
+            return Err(Error::InvalidRequest(format!(
+                "Resource \"{}\" has no ResourceID",
+                resource.resource_name
+            )));
+        }
+        Ok(())
+    }
+
     pub async fn get_message_attachment(&self, attachment_gu: &str) -> Result<DocumentData> {
         let mut params = HashMap::new();
         params.insert("childIntID".to_string(), "".to_string());
@@ -271,36 +794,117 @@ impl StudentVueClient {
         self.parse_attachment_data(&xml_data)
     }
 
-    pub async fn mark_message_read(&self, message_id: &str, message_type: &str) -> Result<String> {
-        let param_str = format!(
-            "&lt;Parms&gt;&lt;MessageListing ID=\"{message_id}\" Type=\"{message_type}\" MarkAsRead=\"true\" /&gt;&lt;/Parms&gt;"
-        );
+    pub async fn get_attachment(&self, attachment: &MessageAttachment) -> Result<DocumentData> {
+        let mut data = self
+            .get_message_attachment(&attachment.attachment_gu)
+            .await?;
+        data.document_gu = attachment.attachment_gu.clone();
+        if data.file_name.is_empty() {
+            data.file_name = attachment.document_name.clone();
+        }
+        Ok(data)
+    }
 
-        let soap_request = SoapClient::create_request(
-            &self.username,
-            &self.password,
-            "PXPWebServices",
-            "UpdatePXPMessage",
-            &param_str,
-            true,
-        );
+    pub async fn mark_message_read(
+        &self,
+        message_id: &str,
+        message_type: &str,
+    ) -> Result<MessageActionResult> {
+        self.update_message(message_id, message_type, MessageAction::MarkRead)
+            .await
+    }
 
-        let endpoint = format!("{}/Service/PXPCommunication.asmx", self.base_url);
+    pub async fn mark_message_unread(
+        &self,
+        message_id: &str,
+        message_type: &str,
+    ) -> Result<MessageActionResult> {
+        self.update_message(message_id, message_type, MessageAction::MarkUnread)
+            .await
+    }
 
-        let response = self
-            .client
-            .post(&endpoint)
-            .header("Content-Type", "text/xml; charset=utf-8")
-            .header(
-                "SOAPAction",
-                "http://edupoint.com/webservices/ProcessWebServiceRequestMultiWeb",
+    pub async fn delete_message(&self, message: &Message) -> Result<MessageActionResult> {
+        self.apply_message_action(message, MessageAction::Delete)
+            .await
+    }
+
+    pub async fn apply_message_action(
+        &self,
+        message: &Message,
+        action: MessageAction,
+    ) -> Result<MessageActionResult> {
+        if action == MessageAction::Delete && !message.deletable {
+            return Ok(MessageActionResult {
+                message_id: message.id.clone(),
+                action,
+                success: false,
+                error: Some("Message is not deletable".to_string()),
+            });
+        }
+
+        self.update_message(&message.id, &message.message_type, action)
+            .await
+    }
+
+    pub async fn apply_message_actions(
+        &self,
+        messages: &[Message],
+        action: MessageAction,
+    ) -> Vec<MessageActionResult> {
+        let mut results = Vec::with_capacity(messages.len());
+        for message in messages {
+            let result = match self.apply_message_action(message, action).await {
+                Ok(result) => result,
+                Err(e) => MessageActionResult {
+                    message_id: message.id.clone(),
+                    action,
+                    success: false,
+                    error: Some(e.to_string()),
+                },
+            };
+            results.push(result);
+        }
+        results
+    }
+
+    async fn update_message(
+        &self,
+        message_id: &str,
+        message_type: &str,
+        action: MessageAction,
+    ) -> Result<MessageActionResult> {
+        let operation = match action {
+            MessageAction::MarkRead => "MarkAsRead=\"true\"",
+            MessageAction::MarkUnread => "MarkAsRead=\"false\"",
+            MessageAction::Delete => "Delete=\"true\"",
+        };
+        let parms = format!(
+            "<Parms><MessageListing ID=\"{}\" Type=\"{}\" {operation} /></Parms>",
+            SoapClient::escape_xml(message_id),
+            SoapClient::escape_xml(message_type),
+        );
+
+        let xml_data = self
+            .make_raw_request(
+                "PXPWebServices",
+                "UpdatePXPMessage",
+                &SoapClient::escape_xml(&parms),
+                true,
+                PROCESS_REQUEST_MULTI_WEB,
             )
-            .body(soap_request)
-            .send()
             .await?;
 
-        let response_text = response.text().await?;
-        SoapClient::parse_response(&response_text)
+        let error = Self::check_rt_error(&xml_data).err().map(|e| match e {
+            Error::InvalidResponse(message) => message,
+            e => e.to_string(),
+        });
+
+        Ok(MessageActionResult {
+            message_id: message_id.to_string(),
+            action,
+            success: error.is_none(),
+            error,
+        })
     }
 
     pub async fn get_student_health_info(
@@ -373,51 +977,43 @@ impl StudentVueClient {
         let mut reader = Reader::from_str(xml);
         reader.trim_text(true);
         let mut buf = Vec::new();
+        let mut current_message: Option<Message> = None;
 
         loop {
             match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.name().as_ref() == b"MessageListing" => {
+                    current_message = Some(Self::parse_message_listing(e));
+                }
                 Ok(Event::Empty(ref e)) if e.name().as_ref() == b"MessageListing" => {
-                    let mut id = String::new();
-                    let mut subject = String::new();
-                    let mut content = String::new();
-                    let mut from = String::new();
-                    let mut date = String::new();
-                    let mut read = false;
-                    let mut deletable = false;
-                    let mut message_type = String::new();
+                    messages.push(Self::parse_message_listing(e));
+                }
+                Ok(Event::Empty(ref e)) if e.name().as_ref() == b"AttachmentData" => {
+                    if let Some(ref mut message) = current_message {
+                        let mut attachment_gu = String::new();
+                        let mut document_name = String::new();
 
-                    for attr in e.attributes().flatten() {
-                        match attr.key.as_ref() {
-                            b"ID" => id = String::from_utf8_lossy(&attr.value).to_string(),
-                            b"Subject" => {
-                                subject = String::from_utf8_lossy(&attr.value).to_string()
-                            }
-                            b"Content" => {
-                                content = String::from_utf8_lossy(&attr.value).to_string()
-                            }
-                            b"From" => from = String::from_utf8_lossy(&attr.value).to_string(),
-                            b"BeginDate" => date = String::from_utf8_lossy(&attr.value).to_string(),
-                            b"Read" => read = String::from_utf8_lossy(&attr.value) == "true",
-                            b"Deletable" => {
-                                deletable = String::from_utf8_lossy(&attr.value) == "true"
-                            }
-                            b"Type" => {
-                                message_type = String::from_utf8_lossy(&attr.value).to_string()
+                        for attr in e.attributes().flatten() {
+                            match attr.key.as_ref() {
+                                b"SmAttachmentGU" => {
+                                    attachment_gu = String::from_utf8_lossy(&attr.value).to_string()
+                                }
+                                b"DocumentName" => {
+                                    document_name = String::from_utf8_lossy(&attr.value).to_string()
+                                }
+                                _ => {}
                             }
-                            _ => {}
                         }
-                    }
 
-                    messages.push(Message {
-                        id,
-                        subject,
-                        content,
-                        from,
-                        date,
-                        read,
-                        deletable,
-                        message_type,
-                    });
+                        message.attachments.push(MessageAttachment {
+                            attachment_gu,
+                            document_name,
+                        });
+                    }
+                }
+                Ok(Event::End(ref e)) if e.name().as_ref() == b"MessageListing" => {
+                    if let Some(message) = current_message.take() {
+                        messages.push(message);
+                    }
                 }
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(Error::XmlParse(e.to_string())),
@@ -429,19 +1025,132 @@ impl StudentVueClient {
         Ok(messages)
     }
 
-    fn parse_calendar(&self, xml: &str) -> Result<Vec<CalendarEvent>> {
-        let mut events = Vec::new();
+    fn parse_message_listing(e: &BytesStart) -> Message {
+        let mut id = String::new();
+        let mut subject = String::new();
+        let mut subject_no_html = String::new();
+        let mut content = String::new();
+        let mut from = String::new();
+        let mut sender_gu = String::new();
+        let mut sender_email = String::new();
+        let mut date = String::new();
+        let mut read = false;
+        let mut deletable = false;
+        let mut message_type = String::new();
+        let mut module_name = String::new();
+
+        for attr in e.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"ID" => id = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Subject" => subject = String::from_utf8_lossy(&attr.value).to_string(),
+                b"SubjectNoHTML" => {
+                    subject_no_html = String::from_utf8_lossy(&attr.value).to_string()
+                }
+                b"Content" => content = String::from_utf8_lossy(&attr.value).to_string(),
+                b"From" => from = String::from_utf8_lossy(&attr.value).to_string(),
+                b"SMMsgPersonGU" => sender_gu = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Email" => sender_email = String::from_utf8_lossy(&attr.value).to_string(),
+                b"BeginDate" => date = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Read" => read = String::from_utf8_lossy(&attr.value) == "true",
+                b"Deletable" => deletable = String::from_utf8_lossy(&attr.value) == "true",
+                b"Type" => message_type = String::from_utf8_lossy(&attr.value).to_string(),
+                b"ModuleName" => module_name = String::from_utf8_lossy(&attr.value).to_string(),
+                _ => {}
+            }
+        }
+
+        Message {
+            id,
+            subject,
+            subject_no_html,
+            content,
+            sender: MessageSender {
+                name: from.clone(),
+                person_gu: sender_gu,
+                email: sender_email,
+            },
+            from,
+            date,
+            read,
+            deletable,
+            message_type,
+            module_name,
+            attachments: Vec::new(),
+        }
+    }
+
+    fn check_rt_error(xml: &str) -> Result<()> {
         let mut reader = Reader::from_str(xml);
         reader.trim_text(true);
         let mut buf = Vec::new();
 
         loop {
             match reader.read_event_into(&mut buf) {
+                Ok(Event::Empty(ref e)) | Ok(Event::Start(ref e))
+                    if e.name().as_ref() == b"RT_ERROR" =>
+                {
+                    let message = e
+                        .attributes()
+                        .flatten()
+                        .find(|attr| attr.key.as_ref() == b"ERROR_MESSAGE")
+                        .map(|attr| String::from_utf8_lossy(&attr.value).to_string())
+                        .unwrap_or_default();
+                    return Err(Error::InvalidResponse(message));
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(Error::XmlParse(e.to_string())),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(())
+    }
+
+    fn parse_calendar(&self, xml: &str) -> Result<CalendarListing> {
+        let mut listing = CalendarListing {
+            school_begin_date: String::new(),
+            school_end_date: String::new(),
+            month_begin_date: String::new(),
+            events: Vec::new(),
+        };
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"CalendarListing" =>
+                {
+                    for attr in e.attributes().flatten() {
+                        match attr.key.as_ref() {
+                            b"SchoolBegDate" => {
+                                listing.school_begin_date =
+                                    String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            b"SchoolEndDate" => {
+                                listing.school_end_date =
+                                    String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            b"MonthBegDate" => {
+                                listing.month_begin_date =
+                                    String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            _ => {}
+                        }
+                    }
+                }
                 Ok(Event::Empty(ref e)) if e.name().as_ref() == b"EventList" => {
                     let mut date = String::new();
                     let mut title = String::new();
                     let mut icon = String::new();
                     let mut day_type = String::new();
+                    let mut start_time = String::new();
+                    let mut link = String::new();
+                    let mut assignment_gu = String::new();
+                    let mut gradebook_gu = String::new();
+                    let mut view_type = String::new();
 
                     for attr in e.attributes().flatten() {
                         match attr.key.as_ref() {
@@ -451,15 +1160,39 @@ impl StudentVueClient {
                             b"DayType" => {
                                 day_type = String::from_utf8_lossy(&attr.value).to_string()
                             }
+                            b"StartTime" => {
+                                start_time = String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            b"Link" => link = String::from_utf8_lossy(&attr.value).to_string(),
+                            b"AGU" => {
+                                assignment_gu = String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            b"DGU" => {
+                                gradebook_gu = String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            b"ViewType" => {
+                                view_type = String::from_utf8_lossy(&attr.value).to_string()
+                            }
                             _ => {}
                         }
                     }
 
-                    events.push(CalendarEvent {
+                    let kind = CalendarEventKind::classify(
+                        &day_type,
+                        &title,
+                        assignment_gu,
+                        gradebook_gu,
+                        view_type,
+                    );
+
+                    listing.events.push(CalendarEvent {
                         date,
                         title,
                         icon,
                         day_type,
+                        start_time,
+                        link,
+                        kind,
                     });
                 }
                 Ok(Event::Eof) => break,
@@ -469,19 +1202,49 @@ impl StudentVueClient {
             buf.clear();
         }
 
-        Ok(events)
+        Ok(listing)
     }
 
-    fn parse_attendance(&self, xml: &str) -> Result<Vec<Absence>> {
-        let mut absences = Vec::new();
+    fn parse_attendance(&self, xml: &str) -> Result<AttendanceReport> {
+        let mut report = AttendanceReport {
+            attendance_type: String::new(),
+            school_name: String::new(),
+            period_count: 0,
+            absences: Vec::new(),
+            total_excused: PeriodTotals::default(),
+            total_tardies: PeriodTotals::default(),
+            total_unexcused: PeriodTotals::default(),
+            total_activities: PeriodTotals::default(),
+            total_unexcused_tardies: PeriodTotals::default(),
+        };
         let mut reader = Reader::from_str(xml);
         reader.trim_text(true);
         let mut buf = Vec::new();
         let mut current_absence: Option<Absence> = None;
         let mut in_periods = false;
+        let mut current_totals: Option<Vec<u8>> = None;
 
         loop {
             match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.name().as_ref() == b"Attendance" => {
+                    for attr in e.attributes().flatten() {
+                        match attr.key.as_ref() {
+                            b"Type" => {
+                                report.attendance_type =
+                                    String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            b"SchoolName" => {
+                                report.school_name =
+                                    String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            b"PeriodCount" => {
+                                report.period_count =
+                                    String::from_utf8_lossy(&attr.value).parse().unwrap_or(0)
+                            }
+                            _ => {}
+                        }
+                    }
+                }
                 Ok(Event::Start(ref e)) if e.name().as_ref() == b"Absence" => {
                     let mut date = String::new();
                     let mut reason = String::new();
@@ -500,6 +1263,7 @@ impl StudentVueClient {
 
                     current_absence = Some(Absence {
                         date,
+                        kind: self.attendance_codes.classify(&reason),
                         reason,
                         note,
                         periods: Vec::new(),
@@ -542,6 +1306,7 @@ impl StudentVueClient {
                         absence.periods.push(AbsencePeriod {
                             number,
                             name,
+                            kind: self.attendance_codes.classify(&reason),
                             reason,
                             course,
                             staff,
@@ -554,8 +1319,51 @@ impl StudentVueClient {
                 }
                 Ok(Event::End(ref e)) if e.name().as_ref() == b"Absence" => {
                     if let Some(absence) = current_absence.take() {
-                        absences.push(absence);
+                        report.absences.push(absence);
+                    }
+                }
+                Ok(Event::Start(ref e))
+                    if matches!(
+                        e.name().as_ref(),
+                        b"TotalExcused"
+                            | b"TotalTardies"
+                            | b"TotalUnexcused"
+                            | b"TotalActivities"
+                            | b"TotalUnexcusedTardies"
+                    ) =>
+                {
+                    current_totals = Some(e.name().as_ref().to_vec());
+                }
+                Ok(Event::Empty(ref e)) if e.name().as_ref() == b"PeriodTotal" => {
+                    let mut number = 0;
+                    let mut total = 0;
+
+                    for attr in e.attributes().flatten() {
+                        match attr.key.as_ref() {
+                            b"Number" => {
+                                number = String::from_utf8_lossy(&attr.value).parse().unwrap_or(0)
+                            }
+                            b"Total" => {
+                                total = String::from_utf8_lossy(&attr.value).parse().unwrap_or(0)
+                            }
+                            _ => {}
+                        }
                     }
+
+                    let totals = match current_totals.as_deref() {
+                        Some(b"TotalExcused") => Some(&mut report.total_excused),
+                        Some(b"TotalTardies") => Some(&mut report.total_tardies),
+                        Some(b"TotalUnexcused") => Some(&mut report.total_unexcused),
+                        Some(b"TotalActivities") => Some(&mut report.total_activities),
+                        Some(b"TotalUnexcusedTardies") => Some(&mut report.total_unexcused_tardies),
+                        _ => None,
+                    };
+                    if let Some(totals) = totals {
+                        totals.periods.push(PeriodTotal { number, total });
+                    }
+                }
+                Ok(Event::End(ref e)) if current_totals.as_deref() == Some(e.name().as_ref()) => {
+                    current_totals = None;
                 }
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(Error::XmlParse(e.to_string())),
@@ -564,45 +1372,102 @@ impl StudentVueClient {
             buf.clear();
         }
 
-        Ok(absences)
+        Ok(report)
     }
 
-    fn parse_gradebook(&self, xml: &str) -> Result<Vec<Course>> {
-        let mut courses = Vec::new();
+    fn parse_gradebook(&self, xml: &str) -> Result<Gradebook> {
+        let mut gradebook = Gradebook::default();
         let mut reader = Reader::from_str(xml);
         reader.trim_text(true);
         let mut buf = Vec::new();
+        let mut current_course: Option<Course> = None;
+        let mut current_mark: Option<Mark> = None;
+        let mut in_assignment = false;
 
         loop {
             match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"ReportPeriod" =>
+                {
+                    gradebook
+                        .reporting_periods
+                        .push(Self::parse_report_period(e));
+                }
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"ReportingPeriod" =>
+                {
+                    gradebook.reporting_period = Some(Self::parse_report_period(e));
+                }
                 Ok(Event::Start(ref e)) if e.name().as_ref() == b"Course" => {
-                    let mut period = String::new();
-                    let mut title = String::new();
-                    let mut room = String::new();
-                    let mut staff = String::new();
-                    let mut staff_email = String::new();
-
-                    for attr in e.attributes().flatten() {
-                        match attr.key.as_ref() {
-                            b"Period" => period = String::from_utf8_lossy(&attr.value).to_string(),
-                            b"Title" => title = String::from_utf8_lossy(&attr.value).to_string(),
-                            b"Room" => room = String::from_utf8_lossy(&attr.value).to_string(),
-                            b"Staff" => staff = String::from_utf8_lossy(&attr.value).to_string(),
-                            b"StaffEMail" => {
-                                staff_email = String::from_utf8_lossy(&attr.value).to_string()
-                            }
-                            _ => {}
+                    current_course = Some(Self::parse_course(e));
+                }
+                Ok(Event::Empty(ref e)) if e.name().as_ref() == b"Course" => {
+                    gradebook.courses.push(Self::parse_course(e));
+                }
+                Ok(Event::End(ref e)) if e.name().as_ref() == b"Course" => {
+                    if let Some(course) = current_course.take() {
+                        gradebook.courses.push(course);
+                    }
+                }
+                Ok(Event::Start(ref e)) if e.name().as_ref() == b"Mark" => {
+                    current_mark = Some(Self::parse_mark(e));
+                }
+                Ok(Event::Empty(ref e)) if e.name().as_ref() == b"Mark" => {
+                    if let Some(ref mut course) = current_course {
+                        course.marks.push(Self::parse_mark(e));
+                    }
+                }
+                Ok(Event::End(ref e)) if e.name().as_ref() == b"Mark" => {
+                    if let (Some(course), Some(mark)) =
+                        (current_course.as_mut(), current_mark.take())
+                    {
+                        course.marks.push(mark);
+                    }
+                }
+                Ok(Event::Start(ref e)) if e.name().as_ref() == b"Assignment" => {
+                    if let Some(ref mut mark) = current_mark {
+                        mark.assignments.push(Self::parse_assignment(e));
+                    }
+                    in_assignment = true;
+                }
+                Ok(Event::Empty(ref e)) if e.name().as_ref() == b"Assignment" => {
+                    if let Some(ref mut mark) = current_mark {
+                        mark.assignments.push(Self::parse_assignment(e));
+                    }
+                }
+                Ok(Event::End(ref e)) if e.name().as_ref() == b"Assignment" => {
+                    in_assignment = false;
+                }
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"Resource" && in_assignment =>
+                {
+                    if let Some(assignment) = current_mark
+                        .as_mut()
+                        .and_then(|mark| mark.assignments.last_mut())
+                    {
+                        assignment
+                            .resources
+                            .push(Self::parse_assignment_resource(e));
+                    }
+                }
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"Standard" =>
+                {
+                    if let Some(ref mut mark) = current_mark {
+                        let standard = Self::parse_standard(e);
+                        if !in_assignment {
+                            mark.standards.push(standard);
+                        } else if let Some(assignment) = mark.assignments.last_mut() {
+                            assignment.standards.push(standard);
                         }
                     }
-
-                    courses.push(Course {
-                        period,
-                        title,
-                        room,
-                        staff,
-                        staff_email,
-                        marks: Vec::new(),
-                    });
+                }
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"AssignmentGradeCalc" =>
+                {
+                    if let Some(ref mut mark) = current_mark {
+                        mark.categories.push(Self::parse_grade_category(e));
+                    }
                 }
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(Error::XmlParse(e.to_string())),
@@ -611,7 +1476,206 @@ impl StudentVueClient {
             buf.clear();
         }
 
-        Ok(courses)
+        Ok(gradebook)
+    }
+
+    fn parse_report_period(e: &BytesStart) -> ReportPeriod {
+        let mut period = ReportPeriod {
+            index: None,
+            name: String::new(),
+            start_date: None,
+            end_date: None,
+        };
+
+        for attr in e.attributes().flatten() {
+            let value = String::from_utf8_lossy(&attr.value).to_string();
+            match attr.key.as_ref() {
+                b"Index" => period.index = value.trim().parse().ok(),
+                b"GradePeriod" => period.name = value,
+                b"StartDate" => period.start_date = parse_date(&value),
+                b"EndDate" => period.end_date = parse_date(&value),
+                _ => {}
+            }
+        }
+
+        period
+    }
+
+    fn parse_course(e: &BytesStart) -> Course {
+        let mut period = String::new();
+        let mut title = String::new();
+        let mut room = String::new();
+        let mut staff = String::new();
+        let mut staff_email = String::new();
+
+        for attr in e.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"Period" => period = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Title" => title = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Room" => room = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Staff" => staff = String::from_utf8_lossy(&attr.value).to_string(),
+                b"StaffEMail" => staff_email = String::from_utf8_lossy(&attr.value).to_string(),
+                _ => {}
+            }
+        }
+
+        Course {
+            period,
+            title,
+            room,
+            staff,
+            staff_email,
+            marks: Vec::new(),
+        }
+    }
+
+    fn parse_mark(e: &BytesStart) -> Mark {
+        let mut mark_name = String::new();
+        let mut score = String::new();
+        let mut score_raw = None;
+
+        for attr in e.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"MarkName" => mark_name = String::from_utf8_lossy(&attr.value).to_string(),
+                b"CalculatedScoreString" => {
+                    score = String::from_utf8_lossy(&attr.value).to_string()
+                }
+                b"CalculatedScoreRaw" => {
+                    score_raw = Some(String::from_utf8_lossy(&attr.value).to_string())
+                }
+                _ => {}
+            }
+        }
+
+        Mark {
+            mark_name,
+            score,
+            score_raw,
+            categories: Vec::new(),
+            standards: Vec::new(),
+            assignments: Vec::new(),
+        }
+    }
+
+    fn parse_standard(e: &BytesStart) -> StandardMark {
+        let mut standard = StandardMark {
+            standard_id: String::new(),
+            subject: String::new(),
+            description: String::new(),
+            proficiency: None,
+            proficiency_label: String::new(),
+            scale_min: None,
+            scale_max: None,
+        };
+
+        for attr in e.attributes().flatten() {
+            let value = String::from_utf8_lossy(&attr.value).to_string();
+            match attr.key.as_ref() {
+                b"StandardID" => standard.standard_id = value,
+                b"Subject" => standard.subject = value,
+                b"Description" => standard.description = value,
+                b"Proficiency" => standard.proficiency = parse_number(&value),
+                b"Mark" => standard.proficiency_label = value,
+                b"ProficiencyMinValue" => standard.scale_min = parse_number(&value),
+                b"ProficiencyMaxValue" => standard.scale_max = parse_number(&value),
+                _ => {}
+            }
+        }
+
+        standard
+    }
+
+    fn parse_grade_category(e: &BytesStart) -> GradeCategory {
+        let mut category = GradeCategory {
+            name: String::new(),
+            weight: None,
+            points: None,
+            points_possible: None,
+            weighted_percent: None,
+            calculated_mark: String::new(),
+        };
+
+        for attr in e.attributes().flatten() {
+            let value = String::from_utf8_lossy(&attr.value).to_string();
+            match attr.key.as_ref() {
+                b"Type" => category.name = value,
+                b"Weight" => category.weight = parse_number(&value),
+                b"Points" => category.points = parse_number(&value),
+                b"PointsPossible" => category.points_possible = parse_number(&value),
+                b"WeightedPct" => category.weighted_percent = parse_number(&value),
+                b"CalculatedMark" => category.calculated_mark = value,
+                _ => {}
+            }
+        }
+
+        category
+    }
+
+    fn parse_assignment(e: &BytesStart) -> Assignment {
+        let mut gradebook_id = String::new();
+        let mut measure = String::new();
+        let mut assignment_type = String::new();
+        let mut date = String::new();
+        let mut due_date = String::new();
+        let mut score = String::new();
+        let mut score_type = String::new();
+        let mut points = String::new();
+        let mut notes = String::new();
+
+        for attr in e.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"GradebookID" => gradebook_id = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Measure" => measure = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Type" => assignment_type = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Date" => date = String::from_utf8_lossy(&attr.value).to_string(),
+                b"DueDate" => due_date = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Score" => score = String::from_utf8_lossy(&attr.value).to_string(),
+                b"ScoreType" => score_type = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Points" => points = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Notes" => notes = String::from_utf8_lossy(&attr.value).to_string(),
+                _ => {}
+            }
+        }
+
+        Assignment {
+            gradebook_id,
+            measure,
+            assignment_type,
+            date,
+            due_date,
+            score,
+            score_type,
+            points,
+            notes,
+            resources: Vec::new(),
+            standards: Vec::new(),
+        }
+    }
+
+    fn parse_assignment_resource(e: &BytesStart) -> AssignmentResource {
+        let mut resource = AssignmentResource {
+            resource_id: String::new(),
+            resource_name: String::new(),
+            resource_type: String::new(),
+            file_name: String::new(),
+            url: String::new(),
+            description: String::new(),
+        };
+
+        for attr in e.attributes().flatten() {
+            let value = String::from_utf8_lossy(&attr.value).to_string();
+            match attr.key.as_ref() {
+                b"ResourceID" => resource.resource_id = value,
+                b"ResourceName" => resource.resource_name = value,
+                b"Type" => resource.resource_type = value,
+                b"FileName" => resource.file_name = value,
+                b"URL" => resource.url = value,
+                b"ResourceDescription" => resource.description = value,
+                _ => {}
+            }
+        }
+
+        resource
     }
 
     fn parse_student_info(&self, xml: &str) -> Result<StudentInfo> {
@@ -738,51 +1802,27 @@ impl StudentVueClient {
         let mut reader = Reader::from_str(xml);
         reader.trim_text(true);
         let mut buf = Vec::new();
+        let mut current_school: Option<SchoolInfo> = None;
 
         loop {
             match reader.read_event_into(&mut buf) {
                 Ok(Event::Empty(ref e)) if e.name().as_ref() == b"StudentSchoolInfoListing" => {
-                    let mut school = String::new();
-                    let mut principal = String::new();
-                    let mut address = String::new();
-                    let mut city = String::new();
-                    let mut state = String::new();
-                    let mut zip = String::new();
-                    let mut phone = String::new();
-                    let mut url = String::new();
-
-                    for attr in e.attributes().flatten() {
-                        match attr.key.as_ref() {
-                            b"School" => school = String::from_utf8_lossy(&attr.value).to_string(),
-                            b"Principal" => {
-                                principal = String::from_utf8_lossy(&attr.value).to_string()
-                            }
-                            b"SchoolAddress" => {
-                                address = String::from_utf8_lossy(&attr.value).to_string()
-                            }
-                            b"SchoolCity" => {
-                                city = String::from_utf8_lossy(&attr.value).to_string()
-                            }
-                            b"SchoolState" => {
-                                state = String::from_utf8_lossy(&attr.value).to_string()
-                            }
-                            b"SchoolZip" => zip = String::from_utf8_lossy(&attr.value).to_string(),
-                            b"Phone" => phone = String::from_utf8_lossy(&attr.value).to_string(),
-                            b"URL" => url = String::from_utf8_lossy(&attr.value).to_string(),
-                            _ => {}
-                        }
+                    return Ok(Self::parse_school_listing(e));
+                }
+                Ok(Event::Start(ref e)) if e.name().as_ref() == b"StudentSchoolInfoListing" => {
+                    current_school = Some(Self::parse_school_listing(e));
+                }
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"StaffList" =>
+                {
+                    if let Some(ref mut school) = current_school {
+                        school.staff.push(Self::parse_staff_member(e));
+                    }
+                }
+                Ok(Event::End(ref e)) if e.name().as_ref() == b"StudentSchoolInfoListing" => {
+                    if let Some(school) = current_school.take() {
+                        return Ok(school);
                     }
-
-                    return Ok(SchoolInfo {
-                        school,
-                        principal,
-                        address,
-                        city,
-                        state,
-                        zip,
-                        phone,
-                        url,
-                    });
                 }
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(Error::XmlParse(e.to_string())),
@@ -791,7 +1831,70 @@ impl StudentVueClient {
             buf.clear();
         }
 
-        Err(Error::InvalidResponse("No school info found".to_string()))
+        current_school.ok_or_else(|| Error::InvalidResponse("No school info found".to_string()))
+    }
+
+    fn parse_school_listing(e: &BytesStart) -> SchoolInfo {
+        let mut school = String::new();
+        let mut principal = String::new();
+        let mut address = String::new();
+        let mut city = String::new();
+        let mut state = String::new();
+        let mut zip = String::new();
+        let mut phone = String::new();
+        let mut url = String::new();
+
+        for attr in e.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"School" => school = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Principal" => principal = String::from_utf8_lossy(&attr.value).to_string(),
+                b"SchoolAddress" => address = String::from_utf8_lossy(&attr.value).to_string(),
+                b"SchoolCity" => city = String::from_utf8_lossy(&attr.value).to_string(),
+                b"SchoolState" => state = String::from_utf8_lossy(&attr.value).to_string(),
+                b"SchoolZip" => zip = String::from_utf8_lossy(&attr.value).to_string(),
+                b"Phone" => phone = String::from_utf8_lossy(&attr.value).to_string(),
+                b"URL" => url = String::from_utf8_lossy(&attr.value).to_string(),
+                _ => {}
+            }
+        }
+
+        SchoolInfo {
+            school,
+            principal,
+            address,
+            city,
+            state,
+            zip,
+            phone,
+            url,
+            staff: Vec::new(),
+        }
+    }
+
+    fn parse_staff_member(e: &BytesStart) -> StaffMember {
+        let mut staff = StaffMember {
+            staff_gu: String::new(),
+            name: String::new(),
+            email: String::new(),
+            title: String::new(),
+            phone: String::new(),
+            extension: String::new(),
+        };
+
+        for attr in e.attributes().flatten() {
+            let value = String::from_utf8_lossy(&attr.value).to_string();
+            match attr.key.as_ref() {
+                b"StaffGU" => staff.staff_gu = value,
+                b"Name" => staff.name = value,
+                b"EMail" => staff.email = value,
+                b"Title" => staff.title = value,
+                b"Phone" => staff.phone = value,
+                b"Extn" => staff.extension = value,
+                _ => {}
+            }
+        }
+
+        staff
     }
 
     fn parse_report_cards(&self, xml: &str) -> Result<Vec<ReportCard>> {
@@ -898,6 +2001,7 @@ impl StudentVueClient {
     }
 
     fn parse_document_data(&self, xml: &str) -> Result<DocumentData> {
+        Self::check_rt_error(xml)?;
         let mut reader = Reader::from_str(xml);
         reader.trim_text(true);
         let mut buf = Vec::new();
@@ -984,12 +2088,146 @@ impl StudentVueClient {
 
         Ok(DocumentData {
             document_gu: String::new(),
+            doc_type: doc_type_for(&file_name, &base64_content),
             file_name,
-            doc_type: "unknown".to_string(),
             base64_content,
         })
     }
 
+    /// Parses the course history listing. Courses are grouped by the enclosing
+    /// `CHSYear` and `CHSTerm` elements; `GPA` elements may appear at the top
+    /// level for cumulative values or inside a year for that year's GPA.
+    fn parse_course_history(&self, xml: &str) -> Result<CourseHistory> {
+        Self::check_rt_error(xml)?;
+
+        let mut history = CourseHistory::default();
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+        let mut buf = Vec::new();
+        let mut school_name = String::new();
+        let mut school_year = String::new();
+        let mut grade = String::new();
+        let mut current_term: Option<HistoryTerm> = None;
+        let reset_year =
+            |fields: [&mut String; 3]| fields.into_iter().for_each(|field| field.clear());
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.name().as_ref() == b"CHSYear" => {
+                    reset_year([&mut school_name, &mut school_year, &mut grade]);
+                    for attr in e.attributes().flatten() {
+                        match attr.key.as_ref() {
+                            b"SchoolName" => {
+                                school_name = String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            b"Year" => {
+                                school_year = String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            b"Grade" => grade = String::from_utf8_lossy(&attr.value).to_string(),
+                            _ => {}
+                        }
+                    }
+                }
+                // An empty year has no terms or GPAs, so like the end of a
+                // year it only resets what the previous one set.
+                Ok(Event::End(ref e)) if e.name().as_ref() == b"CHSYear" => {
+                    reset_year([&mut school_name, &mut school_year, &mut grade]);
+                }
+                Ok(Event::Empty(ref e)) if e.name().as_ref() == b"CHSYear" => {
+                    reset_year([&mut school_name, &mut school_year, &mut grade]);
+                }
+                Ok(Event::Start(ref e)) if e.name().as_ref() == b"CHSTerm" => {
+                    let mut term = HistoryTerm {
+                        school_name: school_name.clone(),
+                        school_year: school_year.clone(),
+                        grade: grade.clone(),
+                        term_name: String::new(),
+                        courses: Vec::new(),
+                    };
+                    for attr in e.attributes().flatten() {
+                        match attr.key.as_ref() {
+                            b"TermName" => {
+                                term.term_name = String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            b"SchoolName" => {
+                                term.school_name = String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            _ => {}
+                        }
+                    }
+                    current_term = Some(term);
+                }
+                Ok(Event::End(ref e)) if e.name().as_ref() == b"CHSTerm" => {
+                    if let Some(term) = current_term.take() {
+                        history.terms.push(term);
+                    }
+                }
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"CHSCourse" =>
+                {
+                    if let Some(ref mut term) = current_term {
+                        term.courses.push(Self::parse_history_course(e));
+                    }
+                }
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"GPA" =>
+                {
+                    let mut gpa = ReportedGpa {
+                        name: String::new(),
+                        value: None,
+                        school_year: school_year.clone(),
+                    };
+                    for attr in e.attributes().flatten() {
+                        match attr.key.as_ref() {
+                            b"GPAName" => {
+                                gpa.name = String::from_utf8_lossy(&attr.value).to_string()
+                            }
+                            b"GPAValue" => {
+                                gpa.value = parse_number(&String::from_utf8_lossy(&attr.value))
+                            }
+                            _ => {}
+                        }
+                    }
+                    history.gpas.push(gpa);
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(Error::XmlParse(e.to_string())),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(history)
+    }
+
+    fn parse_history_course(e: &BytesStart) -> HistoryCourse {
+        let mut course = HistoryCourse {
+            course_id: String::new(),
+            course_title: String::new(),
+            mark: String::new(),
+            credits_attempted: None,
+            credits_earned: None,
+            course_type: String::new(),
+            subject_area: String::new(),
+        };
+
+        for attr in e.attributes().flatten() {
+            let value = String::from_utf8_lossy(&attr.value).to_string();
+            match attr.key.as_ref() {
+                b"CourseID" => course.course_id = value,
+                b"CourseTitle" => course.course_title = value,
+                b"Mark" => course.mark = value,
+                b"CreditsAttempted" => course.credits_attempted = parse_number(&value),
+                b"CreditsCompleted" => course.credits_earned = parse_number(&value),
+                b"CourseType" => course.course_type = value,
+                b"SubjectArea" => course.subject_area = value,
+                _ => {}
+            }
+        }
+
+        course
+    }
+
     fn parse_health_immunizations(&self, xml: &str) -> Result<Vec<HealthImmunization>> {
         let mut immunizations = Vec::new();
         let mut reader = Reader::from_str(xml);
@@ -1054,3 +2292,9 @@ impl StudentVueClient {
         Ok(immunizations)
     }
 }
+
+/// Parses a numeric attribute, tolerating surrounding whitespace and a
+/// trailing percent sign. Blank values are `None`.
+fn parse_number(value: &str) -> Option<f64> {
+    value.trim().trim_end_matches('%').trim().parse().ok()
+}
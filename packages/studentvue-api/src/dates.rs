@@ -0,0 +1,14 @@
+use chrono::{Datelike, NaiveDate};
+
+/// Parses the `M/D/YYYY` dates StudentVue returns, ignoring any trailing time.
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    let date = value.split_whitespace().next()?;
+    NaiveDate::parse_from_str(date, "%m/%d/%Y")
+        .or_else(|_| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+        .ok()
+}
+
+/// Formats a date the way StudentVue expects it in request parameters.
+pub fn format_date(date: NaiveDate) -> String {
+    format!("{}/{}/{}", date.month(), date.day(), date.year())
+}
@@ -0,0 +1,311 @@
+use crate::matching::{same_period, same_text};
+use crate::models::{
+    Absence, Assignment, ClassSchedule, Course, Document, Gradebook, Mark, Message, ReportCard,
+};
+use crate::snapshot::{Snapshot, SnapshotEndpoint};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Something that changed between two snapshots. `before` is `None` when the
+/// item did not exist in the earlier snapshot; for a schedule change, `after`
+/// is `None` when a class was dropped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Change {
+    AssignmentScored {
+        reporting_period: String,
+        course: String,
+        before: Option<Assignment>,
+        after: Assignment,
+    },
+    ScoreChanged {
+        reporting_period: String,
+        course: String,
+        before: Assignment,
+        after: Assignment,
+    },
+    /// A score was taken back: the assignment was graded before and no
+    /// longer is.
+    ScoreRemoved {
+        reporting_period: String,
+        course: String,
+        before: Assignment,
+        after: Assignment,
+    },
+    MarkChanged {
+        reporting_period: String,
+        course: String,
+        before: Option<Mark>,
+        after: Mark,
+    },
+    NewMessage {
+        before: Option<Message>,
+        after: Message,
+    },
+    NewAbsence {
+        before: Option<Absence>,
+        after: Absence,
+    },
+    NewDocument {
+        before: Option<Document>,
+        after: Document,
+    },
+    NewReportCard {
+        before: Option<ReportCard>,
+        after: ReportCard,
+    },
+    ScheduleChanged {
+        before: Option<ClassSchedule>,
+        after: Option<ClassSchedule>,
+    },
+}
+
+/// Compares two snapshots of the same student, oldest first.
+///
+/// An endpoint that failed in either snapshot is skipped rather than treated
+/// as empty, so a failed fetch never shows up as everything being new.
+/// Gradebooks are matched by reporting period name, courses by title and
+/// period, marks by name and assignments by gradebook ID (or name and due date
+/// when there is none). An assignment listed under several marks is reported
+/// once, and one that loses its score is reported as [`Change::ScoreRemoved`].
+/// An absence is reported again when more periods are added to its day.
+pub fn diff(before: &Snapshot, after: &Snapshot) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    if let (Some(old), Some(new)) = (&before.schedule, &after.schedule) {
+        diff_schedule(old, new, &mut changes);
+    }
+
+    let mut seen = HashSet::new();
+    for gradebook in &after.gradebooks {
+        let name = period_name(gradebook);
+        match before
+            .gradebooks
+            .iter()
+            .find(|old| period_name(old) == name)
+        {
+            Some(old) => diff_gradebook(&name, Some(old), gradebook, &mut seen, &mut changes),
+            // A period missing from a snapshot that failed to fetch it is not new.
+            None if before.failed(SnapshotEndpoint::Gradebook) => {}
+            None => diff_gradebook(&name, None, gradebook, &mut seen, &mut changes),
+        }
+    }
+
+    if let (Some(old), Some(new)) = (&before.attendance, &after.attendance) {
+        for absence in &new.absences {
+            let previous = old
+                .absences
+                .iter()
+                .find(|old| old.date.trim() == absence.date.trim());
+            if previous.map_or(true, |previous| {
+                previous.periods.len() < absence.periods.len()
+            }) {
+                changes.push(Change::NewAbsence {
+                    before: previous.cloned(),
+                    after: absence.clone(),
+                });
+            }
+        }
+    }
+
+    if let (Some(old), Some(new)) = (&before.messages, &after.messages) {
+        for message in new {
+            if !old.iter().any(|old| old.id == message.id) {
+                changes.push(Change::NewMessage {
+                    before: None,
+                    after: message.clone(),
+                });
+            }
+        }
+    }
+
+    if let (Some(old), Some(new)) = (&before.documents, &after.documents) {
+        for document in new {
+            if !old
+                .iter()
+                .any(|old| old.document_gu == document.document_gu)
+            {
+                changes.push(Change::NewDocument {
+                    before: None,
+                    after: document.clone(),
+                });
+            }
+        }
+    }
+
+    if let (Some(old), Some(new)) = (&before.report_cards, &after.report_cards) {
+        for report_card in new {
+            if !old
+                .iter()
+                .any(|old| old.document_gu == report_card.document_gu)
+            {
+                changes.push(Change::NewReportCard {
+                    before: None,
+                    after: report_card.clone(),
+                });
+            }
+        }
+    }
+
+    changes
+}
+
+fn diff_schedule(old: &[ClassSchedule], new: &[ClassSchedule], changes: &mut Vec<Change>) {
+    let mut unmatched_new: Vec<&ClassSchedule> = Vec::new();
+    let mut claimed = HashSet::new();
+
+    for entry in new {
+        let previous = old.iter().enumerate().find(|(i, previous)| {
+            !claimed.contains(i)
+                && same_period(&previous.period, &entry.period)
+                && same_text(&previous.course_title, &entry.course_title)
+        });
+        match previous {
+            Some((i, previous)) => {
+                claimed.insert(i);
+                if previous.room_name != entry.room_name
+                    || previous.teacher != entry.teacher
+                    || previous.teacher_email != entry.teacher_email
+                {
+                    changes.push(Change::ScheduleChanged {
+                        before: Some(previous.clone()),
+                        after: Some(entry.clone()),
+                    });
+                }
+            }
+            None => unmatched_new.push(entry),
+        }
+    }
+    let mut unmatched_old: Vec<&ClassSchedule> = old
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !claimed.contains(i))
+        .map(|(_, entry)| entry)
+        .collect();
+
+    // Whatever is left either replaced the class in the same period or was
+    // added or dropped outright.
+    for entry in unmatched_new {
+        let previous = unmatched_old
+            .iter()
+            .position(|previous| same_period(&previous.period, &entry.period))
+            .map(|i| unmatched_old.remove(i));
+        changes.push(Change::ScheduleChanged {
+            before: previous.cloned(),
+            after: Some(entry.clone()),
+        });
+    }
+    for previous in unmatched_old {
+        changes.push(Change::ScheduleChanged {
+            before: Some(previous.clone()),
+            after: None,
+        });
+    }
+}
+
+fn diff_gradebook(
+    reporting_period: &str,
+    old: Option<&Gradebook>,
+    new: &Gradebook,
+    seen: &mut HashSet<(String, String)>,
+    changes: &mut Vec<Change>,
+) {
+    for course in &new.courses {
+        let previous = old.and_then(|old| find_course(&old.courses, course));
+
+        for mark in &course.marks {
+            let previous_mark = previous.and_then(|previous| {
+                previous
+                    .marks
+                    .iter()
+                    .find(|old| same_text(&old.mark_name, &mark.mark_name))
+            });
+            let scored = !mark.score.trim().is_empty() || mark.score_raw.is_some();
+            let changed = previous_mark.map_or(scored, |old| {
+                old.score != mark.score || old.score_raw != mark.score_raw
+            });
+            if changed {
+                changes.push(Change::MarkChanged {
+                    reporting_period: reporting_period.to_string(),
+                    course: course.title.clone(),
+                    before: previous_mark.cloned(),
+                    after: mark.clone(),
+                });
+            }
+
+            for assignment in &mark.assignments {
+                if !seen.insert((course.title.to_lowercase(), assignment_key(assignment))) {
+                    continue;
+                }
+                let previous_assignment = previous.and_then(|previous| {
+                    previous
+                        .marks
+                        .iter()
+                        .flat_map(|mark| &mark.assignments)
+                        .find(|old| assignment_key(old) == assignment_key(assignment))
+                });
+
+                match previous_assignment {
+                    Some(old)
+                        if old.is_graded()
+                            && assignment.is_graded()
+                            && (old.score != assignment.score
+                                || old.points != assignment.points) =>
+                    {
+                        changes.push(Change::ScoreChanged {
+                            reporting_period: reporting_period.to_string(),
+                            course: course.title.clone(),
+                            before: old.clone(),
+                            after: assignment.clone(),
+                        });
+                    }
+                    Some(old) if old.is_graded() && !assignment.is_graded() => {
+                        changes.push(Change::ScoreRemoved {
+                            reporting_period: reporting_period.to_string(),
+                            course: course.title.clone(),
+                            before: old.clone(),
+                            after: assignment.clone(),
+                        });
+                    }
+                    Some(old) if old.is_graded() => {}
+                    _ if assignment.is_graded() => changes.push(Change::AssignmentScored {
+                        reporting_period: reporting_period.to_string(),
+                        course: course.title.clone(),
+                        before: previous_assignment.cloned(),
+                        after: assignment.clone(),
+                    }),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+fn find_course<'a>(courses: &'a [Course], course: &Course) -> Option<&'a Course> {
+    courses
+        .iter()
+        .find(|old| {
+            same_text(&old.title, &course.title) && same_period(&old.period, &course.period)
+        })
+        .or_else(|| {
+            courses
+                .iter()
+                .find(|old| same_text(&old.title, &course.title))
+        })
+}
+
+fn period_name(gradebook: &Gradebook) -> String {
+    gradebook
+        .reporting_period
+        .as_ref()
+        .map(|period| period.name.clone())
+        .unwrap_or_default()
+}
+
+fn assignment_key(assignment: &Assignment) -> String {
+    if assignment.gradebook_id.is_empty() {
+        format!("{}|{}", assignment.measure, assignment.due_date)
+    } else {
+        assignment.gradebook_id.clone()
+    }
+}
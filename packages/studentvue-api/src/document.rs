@@ -0,0 +1,524 @@
+use crate::error::{Error, Result};
+use crate::models::DocumentData;
+use crate::soap::SoapClient;
+use base64::engine::general_purpose;
+use base64::Engine;
+use quick_xml::escape::unescape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+#[derive(Debug, Clone)]
+pub struct DecodedDocument {
+    pub file_name: String,
+    pub mime_type: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+impl DocumentData {
+    /// Decodes the base64 payload, detects its MIME type from the leading
+    /// bytes and returns a file name that is safe to create on disk.
+    pub fn decode(&self) -> Result<DecodedDocument> {
+        let bytes = decode_base64(&self.base64_content)?;
+        let mime_type = detect_mime(&bytes, &self.file_name);
+
+        Ok(DecodedDocument {
+            file_name: file_name_for(&self.file_name, mime_type),
+            mime_type,
+            bytes,
+        })
+    }
+
+    /// Decodes the document and writes it into `dir`, returning the path it was
+    /// written to. An existing file is never overwritten; a numbered suffix is
+    /// added instead.
+    pub fn save_to(&self, dir: impl AsRef<Path>) -> Result<PathBuf> {
+        let document = self.decode()?;
+        write_new_file(dir.as_ref(), &document.file_name, &document.bytes)
+    }
+}
+
+pub(crate) fn decode_base64(content: &str) -> Result<Vec<u8>> {
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+    Ok(general_purpose::STANDARD.decode(compact)?)
+}
+
+/// Sanitizes a portal-supplied file name and adds the extension for
+/// `mime_type` when it has none.
+pub(crate) fn file_name_for(file_name: &str, mime_type: &str) -> String {
+    let mut file_name = sanitize_file_name(file_name);
+    if Path::new(&file_name).extension().is_none() {
+        if let Some(extension) = extension_for(mime_type) {
+            file_name.push('.');
+            file_name.push_str(extension);
+        }
+    }
+    file_name
+}
+
+/// Detects the MIME type of a file on disk from its first few kilobytes, so
+/// large downloads never have to be read back into memory.
+pub(crate) fn detect_file_mime(path: &Path, file_name: &str) -> Result<&'static str> {
+    let mut head = Vec::new();
+    File::open(path)?
+        .take(MIME_HEAD_LEN)
+        .read_to_end(&mut head)?;
+    // Cutting the head off mid-character must not make a text file look binary.
+    if let Err(e) = std::str::from_utf8(&head) {
+        if e.error_len().is_none() {
+            head.truncate(e.valid_up_to());
+        }
+    }
+    Ok(detect_mime(&head, file_name))
+}
+
+/// Creates `file_name` inside `dir` without overwriting anything, adding
+/// ` (1)`, ` (2)`, ... before the extension on collision.
+pub(crate) fn write_new_file(dir: &Path, file_name: &str, bytes: &[u8]) -> Result<PathBuf> {
+    let (path, mut file) = create_new_file(dir, file_name)?;
+    file.write_all(bytes)?;
+    Ok(path)
+}
+
+/// Opens a new, empty `file_name` inside `dir`, numbered like
+/// [`write_new_file`] when the name is taken.
+pub(crate) fn create_new_file(dir: &Path, file_name: &str) -> Result<(PathBuf, File)> {
+    let file_name = sanitize_file_name(file_name);
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (file_name.as_str(), None),
+    };
+
+    let mut attempt = 0;
+    loop {
+        let candidate = match (attempt, extension) {
+            (0, _) => file_name.clone(),
+            (n, Some(extension)) => format!("{stem} ({n}).{extension}"),
+            (n, None) => format!("{stem} ({n})"),
+        };
+        let path = dir.join(candidate);
+
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => attempt += 1,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Reduces a portal-supplied file name to a single safe path component.
+pub fn sanitize_file_name(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or("");
+    let cleaned: String = base
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.trim_matches(|c: char| c == '.' || c.is_whitespace());
+
+    if cleaned.is_empty() {
+        return "document".to_string();
+    }
+
+    let stem = cleaned.split('.').next().unwrap_or("").to_ascii_uppercase();
+    let reserved = matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+        || ((stem.starts_with("COM") || stem.starts_with("LPT"))
+            && stem.len() == 4
+            && stem.as_bytes()[3].is_ascii_digit());
+    let cleaned = if reserved {
+        format!("_{cleaned}")
+    } else {
+        cleaned.to_string()
+    };
+
+    truncate_file_name(&cleaned, 200)
+}
+
+fn truncate_file_name(name: &str, max_len: usize) -> String {
+    if name.len() <= max_len {
+        return name.to_string();
+    }
+
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension)
+        .filter(|extension| extension.len() <= 10)
+        .unwrap_or("");
+    let mut stem_len = if extension.is_empty() {
+        max_len
+    } else {
+        max_len - extension.len() - 1
+    };
+    while !name.is_char_boundary(stem_len) {
+        stem_len -= 1;
+    }
+
+    if extension.is_empty() {
+        name[..stem_len].to_string()
+    } else {
+        format!("{}.{extension}", &name[..stem_len])
+    }
+}
+
+/// Detects a MIME type from magic bytes, using the file name only to tell
+/// apart formats that share a container (such as Office documents in ZIP).
+pub fn detect_mime(bytes: &[u8], file_name: &str) -> &'static str {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    if bytes.starts_with(b"%PDF-") {
+        "application/pdf"
+    } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "image/jpeg"
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        "image/gif"
+    } else if bytes.starts_with(b"BM") && bytes.len() > 14 {
+        "image/bmp"
+    } else if bytes.starts_with(b"II*\0") || bytes.starts_with(b"MM\0*") {
+        "image/tiff"
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        "image/webp"
+    } else if bytes.starts_with(b"{\\rtf") {
+        "application/rtf"
+    } else if bytes.starts_with(b"PK\x03\x04") {
+        match extension.as_str() {
+            "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+            _ => "application/zip",
+        }
+    } else if bytes.starts_with(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]) {
+        match extension.as_str() {
+            "xls" => "application/vnd.ms-excel",
+            "ppt" => "application/vnd.ms-powerpoint",
+            _ => "application/msword",
+        }
+    } else if std::str::from_utf8(bytes).is_ok() {
+        match extension.as_str() {
+            "html" | "htm" => "text/html",
+            "csv" => "text/csv",
+            _ => "text/plain",
+        }
+    } else {
+        "application/octet-stream"
+    }
+}
+
+pub fn extension_for(mime_type: &str) -> Option<&'static str> {
+    let extension = match mime_type {
+        "application/pdf" => "pdf",
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/bmp" => "bmp",
+        "image/tiff" => "tiff",
+        "image/webp" => "webp",
+        "application/rtf" => "rtf",
+        "application/zip" => "zip",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => "docx",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => "xlsx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation" => "pptx",
+        "application/msword" => "doc",
+        "application/vnd.ms-excel" => "xls",
+        "application/vnd.ms-powerpoint" => "ppt",
+        "text/html" => "html",
+        "text/csv" => "csv",
+        "text/plain" => "txt",
+        _ => return None,
+    };
+    Some(extension)
+}
+
+/// Derives the short document type the portal reports for other documents
+/// (such as `PDF`) from the file name, or from the content when the name has
+/// no extension.
+pub(crate) fn doc_type_for(file_name: &str, base64_content: &str) -> String {
+    if let Some(extension) = Path::new(file_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+    {
+        return extension.to_ascii_uppercase();
+    }
+
+    let mut head: Vec<u8> = base64_content
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .take(64)
+        .collect();
+    head.truncate(head.len() - head.len() % 4);
+    general_purpose::STANDARD
+        .decode(head)
+        .ok()
+        .and_then(|bytes| extension_for(detect_mime(&bytes, file_name)))
+        .map(|extension| extension.to_ascii_uppercase())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamedDocument {
+    pub document_gu: String,
+    pub file_name: String,
+    pub doc_type: String,
+    pub bytes_written: u64,
+}
+
+const MAX_HEADER_LEN: usize = 1024 * 1024;
+const DECODE_BATCH_LEN: usize = 64 * 1024;
+const SNIFF_LEN: usize = 16;
+const MIME_HEAD_LEN: u64 = 4096;
+
+enum ExtractState {
+    Header,
+    Body,
+    Done,
+}
+
+/// Pulls the `<Base64Code>` payload out of a SOAP document response as it
+/// arrives, decoding it in fixed-size batches so that memory use does not grow
+/// with the size of the document.
+///
+/// The document XML is entity-escaped inside the SOAP result, so the payload
+/// is delimited by `&lt;Base64Code&gt;` and `&lt;/Base64Code&gt;`; base64 text
+/// itself never needs escaping and is passed through unchanged.
+pub(crate) struct Base64Extractor<W> {
+    writer: W,
+    state: ExtractState,
+    header: Vec<u8>,
+    carry: Vec<u8>,
+    pending: Vec<u8>,
+    sniff: Vec<u8>,
+    bytes_written: u64,
+}
+
+impl<W: AsyncWrite + Unpin> Base64Extractor<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        Self {
+            writer,
+            state: ExtractState::Header,
+            header: Vec::new(),
+            carry: Vec::new(),
+            pending: Vec::new(),
+            sniff: Vec::new(),
+            bytes_written: 0,
+        }
+    }
+
+    pub(crate) async fn push(&mut self, chunk: &[u8]) -> Result<()> {
+        match self.state {
+            ExtractState::Header => {
+                self.header.extend_from_slice(chunk);
+                let markers: [&[u8]; 2] = [b"&lt;Base64Code&gt;", b"<Base64Code>"];
+                let found = markers.iter().find_map(|marker| {
+                    find_bytes(&self.header, marker).map(|start| (start, start + marker.len()))
+                });
+
+                match found {
+                    Some((start, end)) => {
+                        let body = self.header.split_off(end);
+                        self.header.truncate(start);
+                        self.state = ExtractState::Body;
+                        self.push_body(&body).await
+                    }
+                    None if self.header.len() > MAX_HEADER_LEN => Err(Error::InvalidResponse(
+                        "Document response has no Base64Code element".to_string(),
+                    )),
+                    None => Ok(()),
+                }
+            }
+            ExtractState::Body => self.push_body(chunk).await,
+            ExtractState::Done => Ok(()),
+        }
+    }
+
+    async fn push_body(&mut self, chunk: &[u8]) -> Result<()> {
+        let mut data = std::mem::take(&mut self.carry);
+        data.extend_from_slice(chunk);
+
+        let mut i = 0;
+        while i < data.len() {
+            match data[i] {
+                b'<' => {
+                    self.state = ExtractState::Done;
+                    break;
+                }
+                b'&' => {
+                    if data[i..].starts_with(b"&lt;") {
+                        self.state = ExtractState::Done;
+                        break;
+                    }
+                    // Line breaks arrive as numeric references such as `&#xD;`,
+                    // which the SOAP layer escapes again as `&amp;#xD;`.
+                    let from = if data[i..].starts_with(b"&amp;") {
+                        5
+                    } else {
+                        0
+                    };
+                    let Some(end) = data[i + from..].iter().position(|b| *b == b';') else {
+                        self.carry = data[i..].to_vec();
+                        break;
+                    };
+                    i += from + end + 1;
+                    continue;
+                }
+                b if b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=') => {
+                    self.pending.push(b)
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+
+        if matches!(self.state, ExtractState::Done) {
+            self.flush_pending(true).await
+        } else if self.pending.len() >= DECODE_BATCH_LEN {
+            self.flush_pending(false).await
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn flush_pending(&mut self, last: bool) -> Result<()> {
+        let len = if last {
+            self.pending.len()
+        } else {
+            self.pending.len() - self.pending.len() % 4
+        };
+        let decoded = general_purpose::STANDARD.decode(&self.pending[..len])?;
+        self.pending.drain(..len);
+
+        if self.sniff.len() < SNIFF_LEN {
+            let take = (SNIFF_LEN - self.sniff.len()).min(decoded.len());
+            self.sniff.extend_from_slice(&decoded[..take]);
+        }
+        self.writer.write_all(&decoded).await?;
+        self.bytes_written += decoded.len() as u64;
+        Ok(())
+    }
+
+    /// Completes the download and returns the document metadata found before
+    /// the payload.
+    pub(crate) async fn finish(mut self, document_gu: &str) -> Result<StreamedDocument> {
+        match self.state {
+            ExtractState::Header => {
+                let text = String::from_utf8_lossy(&self.header).to_string();
+                let xml = SoapClient::parse_response(&text)?;
+                return Err(Error::InvalidResponse(rt_error(&xml).unwrap_or_else(
+                    || "Document response has no Base64Code element".to_string(),
+                )));
+            }
+            ExtractState::Body => {
+                return Err(Error::InvalidResponse(
+                    "Document response ended before the Base64Code element closed".to_string(),
+                ))
+            }
+            ExtractState::Done => {}
+        }
+        self.writer.flush().await?;
+
+        let header = String::from_utf8_lossy(&self.header).to_string();
+        let (file_name, doc_type, gu) = document_attributes(&header);
+
+        Ok(StreamedDocument {
+            document_gu: if gu.is_empty() {
+                document_gu.to_string()
+            } else {
+                gu
+            },
+            doc_type: if doc_type.is_empty() {
+                Path::new(&file_name)
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .map(|extension| extension.to_string())
+                    .or_else(|| {
+                        extension_for(detect_mime(&self.sniff, &file_name)).map(String::from)
+                    })
+                    .map(|extension| extension.to_ascii_uppercase())
+                    .unwrap_or_else(|| "unknown".to_string())
+            } else {
+                doc_type
+            },
+            file_name,
+            bytes_written: self.bytes_written,
+        })
+    }
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn document_attributes(header: &str) -> (String, String, String) {
+    let inner = header
+        .rfind("Result>")
+        .map(|i| &header[i + "Result>".len()..])
+        .unwrap_or(header);
+    let inner = unescape(inner)
+        .map(|inner| inner.to_string())
+        .unwrap_or_else(|_| inner.to_string());
+
+    let mut file_name = String::new();
+    let mut doc_type = String::new();
+    let mut document_gu = String::new();
+    let mut reader = Reader::from_str(&inner);
+    let mut buf = Vec::new();
+
+    while let Ok(event) = reader.read_event_into(&mut buf) {
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                for attr in e.attributes().flatten() {
+                    match attr.key.as_ref() {
+                        b"FileName" | b"DocumentName" => {
+                            file_name = String::from_utf8_lossy(&attr.value).to_string()
+                        }
+                        b"DocType" => doc_type = String::from_utf8_lossy(&attr.value).to_string(),
+                        b"DocumentGU" => {
+                            document_gu = String::from_utf8_lossy(&attr.value).to_string()
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    (file_name, doc_type, document_gu)
+}
+
+fn rt_error(xml: &str) -> Option<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut buf = Vec::new();
+
+    while let Ok(event) = reader.read_event_into(&mut buf) {
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) if e.name().as_ref() == b"RT_ERROR" => {
+                return e
+                    .attributes()
+                    .flatten()
+                    .find(|attr| attr.key.as_ref() == b"ERROR_MESSAGE")
+                    .map(|attr| String::from_utf8_lossy(&attr.value).to_string());
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    None
+}
@@ -14,8 +14,20 @@ pub enum Error {
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
 
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
     #[error("Decode error: {0}")]
     Decode(#[from] base64::DecodeError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Archive error: {0}")]
+    Archive(#[from] zip::result::ZipError),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
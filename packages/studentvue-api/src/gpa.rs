@@ -0,0 +1,336 @@
+use crate::models::{Course, CourseHistory};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum CourseLevel {
+    #[default]
+    Regular,
+    Honors,
+    AdvancedPlacement,
+    InternationalBaccalaureate,
+}
+
+/// Maps letter marks to grade points. Lookups ignore case and surrounding
+/// whitespace; marks missing from the scale (such as `P` or `I`) are left out
+/// of the GPA entirely.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradeScale {
+    points: HashMap<String, f64>,
+}
+
+impl Default for GradeScale {
+    fn default() -> Self {
+        let mut scale = Self::empty();
+        for (mark, points) in [
+            ("A+", 4.0),
+            ("A", 4.0),
+            ("A-", 3.7),
+            ("B+", 3.3),
+            ("B", 3.0),
+            ("B-", 2.7),
+            ("C+", 2.3),
+            ("C", 2.0),
+            ("C-", 1.7),
+            ("D+", 1.3),
+            ("D", 1.0),
+            ("D-", 0.7),
+            ("E", 0.0),
+            ("F", 0.0),
+        ] {
+            scale.insert(mark, points);
+        }
+        scale
+    }
+}
+
+impl GradeScale {
+    pub fn empty() -> Self {
+        Self {
+            points: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, mark: &str, points: f64) -> &mut Self {
+        self.points.insert(normalize(mark), points);
+        self
+    }
+
+    pub fn with(mut self, mark: &str, points: f64) -> Self {
+        self.insert(mark, points);
+        self
+    }
+
+    /// Returns the points for a mark such as `"B+"`. Scores that carry a
+    /// percentage after the letter, like `"B+ (88.5%)"`, use the letter.
+    pub fn points_for(&self, mark: &str) -> Option<f64> {
+        let mark = normalize(mark);
+        self.points.get(&mark).copied().or_else(|| {
+            mark.split_whitespace()
+                .next()
+                .and_then(|letter| self.points.get(letter))
+                .copied()
+        })
+    }
+}
+
+/// A course as the calculator sees it, built from either the current
+/// gradebook or the course history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpaCourse {
+    pub term: String,
+    pub course_id: String,
+    pub title: String,
+    pub mark: String,
+    pub credits: Option<f64>,
+    pub course_type: String,
+}
+
+impl GpaCourse {
+    /// Uses each course's most recent mark with a score, labelling all of them
+    /// with `term`.
+    pub fn from_gradebook(courses: &[Course], term: &str) -> Vec<Self> {
+        courses
+            .iter()
+            .filter_map(|course| {
+                let mark = course
+                    .marks
+                    .iter()
+                    .rev()
+                    .find(|mark| !mark.score.trim().is_empty())?;
+                Some(Self {
+                    term: term.to_string(),
+                    course_id: String::new(),
+                    title: course.title.clone(),
+                    mark: mark.score.clone(),
+                    credits: None,
+                    course_type: String::new(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn from_history(history: &CourseHistory) -> Vec<Self> {
+        history
+            .courses()
+            .map(|(term, course)| Self {
+                term: format!("{} {}", term.school_year, term.term_name)
+                    .trim()
+                    .to_string(),
+                course_id: course.course_id.clone(),
+                title: course.course_title.clone(),
+                mark: course.mark.clone(),
+                credits: course.credits_attempted,
+                course_type: course.course_type.clone(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Gpa {
+    pub weighted: Option<f64>,
+    pub unweighted: Option<f64>,
+    pub credits: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermGpa {
+    pub term: String,
+    pub gpa: Gpa,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpaReport {
+    pub terms: Vec<TermGpa>,
+    pub cumulative: Gpa,
+}
+
+/// Computes credit-weighted GPAs using a [`GradeScale`] and per-level bonuses.
+///
+/// A course's level comes from custom title patterns first, then from its
+/// course type flag, then from words in the title such as `AP`, `IB` or
+/// `Honors`. Failing marks never receive a bonus.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpaCalculator {
+    pub scale: GradeScale,
+    pub honors_bonus: f64,
+    pub ap_bonus: f64,
+    pub ib_bonus: f64,
+    pub default_credits: f64,
+    level_patterns: Vec<(String, CourseLevel)>,
+    credit_overrides: HashMap<String, f64>,
+}
+
+impl Default for GpaCalculator {
+    fn default() -> Self {
+        Self {
+            scale: GradeScale::default(),
+            honors_bonus: 0.5,
+            ap_bonus: 1.0,
+            ib_bonus: 1.0,
+            default_credits: 1.0,
+            level_patterns: Vec::new(),
+            credit_overrides: HashMap::new(),
+        }
+    }
+}
+
+impl GpaCalculator {
+    pub fn new(scale: GradeScale) -> Self {
+        Self {
+            scale,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_bonuses(mut self, honors: f64, ap: f64, ib: f64) -> Self {
+        self.honors_bonus = honors;
+        self.ap_bonus = ap;
+        self.ib_bonus = ib;
+        self
+    }
+
+    /// Treats every course whose title contains `pattern` (ignoring case) as
+    /// `level`.
+    pub fn with_level_pattern(mut self, pattern: &str, level: CourseLevel) -> Self {
+        self.level_patterns.push((pattern.to_lowercase(), level));
+        self
+    }
+
+    /// Sets the credit weight for a course, matched by course ID or title.
+    pub fn with_credits(mut self, course: &str, credits: f64) -> Self {
+        self.credit_overrides.insert(normalize(course), credits);
+        self
+    }
+
+    pub fn level_of(&self, course: &GpaCourse) -> CourseLevel {
+        let title = course.title.to_lowercase();
+        if let Some((_, level)) = self
+            .level_patterns
+            .iter()
+            .find(|(pattern, _)| title.contains(pattern.as_str()))
+        {
+            return *level;
+        }
+
+        level_from_words(&course.course_type)
+            .or_else(|| level_from_words(&course.title))
+            .unwrap_or_default()
+    }
+
+    pub fn credits_for(&self, course: &GpaCourse) -> f64 {
+        [&course.course_id, &course.title]
+            .iter()
+            .filter(|key| !key.is_empty())
+            .find_map(|key| self.credit_overrides.get(&normalize(key)))
+            .copied()
+            .or(course.credits)
+            .unwrap_or(self.default_credits)
+    }
+
+    fn bonus_for(&self, level: CourseLevel) -> f64 {
+        match level {
+            CourseLevel::Regular => 0.0,
+            CourseLevel::Honors => self.honors_bonus,
+            CourseLevel::AdvancedPlacement => self.ap_bonus,
+            CourseLevel::InternationalBaccalaureate => self.ib_bonus,
+        }
+    }
+
+    pub fn calculate(&self, courses: &[GpaCourse]) -> GpaReport {
+        let mut terms: Vec<(String, Totals)> = Vec::new();
+        let mut cumulative = Totals::default();
+
+        for course in courses {
+            let Some(points) = self.scale.points_for(&course.mark) else {
+                continue;
+            };
+            let credits = self.credits_for(course);
+            if credits <= 0.0 {
+                continue;
+            }
+            let bonus = if points > 0.0 {
+                self.bonus_for(self.level_of(course))
+            } else {
+                0.0
+            };
+
+            let index = match terms.iter().position(|(term, _)| *term == course.term) {
+                Some(index) => index,
+                None => {
+                    terms.push((course.term.clone(), Totals::default()));
+                    terms.len() - 1
+                }
+            };
+            terms[index].1.add(points, bonus, credits);
+            cumulative.add(points, bonus, credits);
+        }
+
+        GpaReport {
+            terms: terms
+                .into_iter()
+                .map(|(term, totals)| TermGpa {
+                    term,
+                    gpa: totals.gpa(),
+                })
+                .collect(),
+            cumulative: cumulative.gpa(),
+        }
+    }
+
+    pub fn gradebook_gpa(&self, courses: &[Course], term: &str) -> GpaReport {
+        self.calculate(&GpaCourse::from_gradebook(courses, term))
+    }
+
+    pub fn history_gpa(&self, history: &CourseHistory) -> GpaReport {
+        self.calculate(&GpaCourse::from_history(history))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Totals {
+    unweighted: f64,
+    weighted: f64,
+    credits: f64,
+}
+
+impl Totals {
+    fn add(&mut self, points: f64, bonus: f64, credits: f64) {
+        self.unweighted += points * credits;
+        self.weighted += (points + bonus) * credits;
+        self.credits += credits;
+    }
+
+    fn gpa(&self) -> Gpa {
+        let average = |total: f64| (self.credits > 0.0).then(|| total / self.credits);
+        Gpa {
+            weighted: average(self.weighted),
+            unweighted: average(self.unweighted),
+            credits: self.credits,
+        }
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.trim().to_uppercase()
+}
+
+fn level_from_words(text: &str) -> Option<CourseLevel> {
+    let lower = text.to_lowercase();
+    if lower.contains("advanced placement") {
+        return Some(CourseLevel::AdvancedPlacement);
+    }
+    if lower.contains("international baccalaureate") {
+        return Some(CourseLevel::InternationalBaccalaureate);
+    }
+
+    lower
+        .split(|c: char| !c.is_alphanumeric())
+        .find_map(|word| match word {
+            "ap" => Some(CourseLevel::AdvancedPlacement),
+            "ib" => Some(CourseLevel::InternationalBaccalaureate),
+            "honors" | "hon" => Some(CourseLevel::Honors),
+            _ => None,
+        })
+}
@@ -0,0 +1,336 @@
+use crate::dates::parse_date;
+use crate::models::{CalendarEvent, CalendarEventKind, ClassSchedule, Course};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc, Weekday};
+use std::collections::HashSet;
+
+/// When a class period meets, used to turn the class schedule into recurring
+/// events.
+#[derive(Debug, Clone)]
+pub struct BellTime {
+    pub period: String,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClassMeetings {
+    pub first_day: NaiveDate,
+    pub last_day: NaiveDate,
+    pub weekdays: Vec<Weekday>,
+    pub bell_times: Vec<BellTime>,
+    pub skip_dates: Vec<NaiveDate>,
+}
+
+#[derive(Debug, Clone)]
+struct IcsEvent {
+    uid: String,
+    summary: String,
+    description: String,
+    location: String,
+    url: String,
+    timing: Timing,
+}
+
+#[derive(Debug, Clone)]
+enum Timing {
+    AllDay(NaiveDate),
+    Recurring {
+        first_day: NaiveDate,
+        start: NaiveTime,
+        end: NaiveTime,
+        last_day: NaiveDate,
+        weekdays: Vec<Weekday>,
+        skip_dates: Vec<NaiveDate>,
+    },
+}
+
+/// Builds an RFC 5545 calendar from school calendar events, gradebook due
+/// dates and class meetings.
+///
+/// UIDs are derived from the portal's identifiers (or from the event content
+/// when there is none), so re-importing an export updates existing entries
+/// instead of duplicating them. An assignment that appears both on the school
+/// calendar and in the gradebook is only exported once.
+#[derive(Debug, Clone)]
+pub struct IcsCalendar {
+    name: String,
+    timestamp: DateTime<Utc>,
+    events: Vec<IcsEvent>,
+    uids: HashSet<String>,
+}
+
+impl IcsCalendar {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            timestamp: Utc::now(),
+            events: Vec::new(),
+            uids: HashSet::new(),
+        }
+    }
+
+    pub fn with_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn add_calendar_events(&mut self, events: &[CalendarEvent]) -> &mut Self {
+        for event in events {
+            let Some(date) = parse_date(&event.date) else {
+                continue;
+            };
+
+            let uid = match event.assignment_gu() {
+                Some(assignment_gu) => assignment_uid(assignment_gu),
+                None => format!(
+                    "event-{}-{:016x}@studentvue",
+                    date.format("%Y%m%d"),
+                    fnv1a(event.title.as_bytes())
+                ),
+            };
+            let description = match &event.kind {
+                CalendarEventKind::Assignment { .. } => "Assignment due".to_string(),
+                CalendarEventKind::Holiday => "No school".to_string(),
+                _ => String::new(),
+            };
+
+            self.push(IcsEvent {
+                uid,
+                summary: event.title.clone(),
+                description,
+                location: String::new(),
+                url: event.link.clone(),
+                timing: Timing::AllDay(date),
+            });
+        }
+        self
+    }
+
+    pub fn add_assignments(&mut self, courses: &[Course]) -> &mut Self {
+        for course in courses {
+            for mark in &course.marks {
+                for assignment in &mark.assignments {
+                    let Some(due) = parse_date(&assignment.due_date) else {
+                        continue;
+                    };
+
+                    let uid = if assignment.gradebook_id.is_empty() {
+                        format!(
+                            "assignment-{:016x}@studentvue",
+                            fnv1a(
+                                format!("{}|{}|{}", course.title, assignment.measure, due)
+                                    .as_bytes()
+                            )
+                        )
+                    } else {
+                        assignment_uid(&assignment.gradebook_id)
+                    };
+
+                    let mut description =
+                        format!("{} ({})", assignment.assignment_type, course.staff);
+                    if !assignment.points.is_empty() {
+                        description.push_str(&format!("\nPoints: {}", assignment.points));
+                    }
+                    if !assignment.score.is_empty() {
+                        description.push_str(&format!("\nScore: {}", assignment.score));
+                    }
+
+                    self.push(IcsEvent {
+                        uid,
+                        summary: format!("{}: {}", course.title, assignment.measure),
+                        description,
+                        location: String::new(),
+                        url: String::new(),
+                        timing: Timing::AllDay(due),
+                    });
+                }
+            }
+        }
+        self
+    }
+
+    pub fn add_class_meetings(
+        &mut self,
+        schedule: &[ClassSchedule],
+        meetings: &ClassMeetings,
+    ) -> &mut Self {
+        for class in schedule {
+            let Some(bell) = meetings
+                .bell_times
+                .iter()
+                .find(|bell| bell.period == class.period)
+            else {
+                continue;
+            };
+
+            let Some(first_day) = meetings
+                .first_day
+                .iter_days()
+                .take_while(|day| *day <= meetings.last_day)
+                .find(|day| meetings.weekdays.contains(&day.weekday()))
+            else {
+                continue;
+            };
+
+            self.push(IcsEvent {
+                uid: format!(
+                    "class-{}-{:016x}@studentvue",
+                    class.period,
+                    fnv1a(class.course_title.as_bytes())
+                ),
+                summary: class.course_title.clone(),
+                description: format!("Period {} with {}", class.period, class.teacher),
+                location: class.room_name.clone(),
+                url: String::new(),
+                timing: Timing::Recurring {
+                    first_day,
+                    start: bell.start,
+                    end: bell.end,
+                    last_day: meetings.last_day,
+                    weekdays: meetings.weekdays.clone(),
+                    skip_dates: meetings.skip_dates.clone(),
+                },
+            });
+        }
+        self
+    }
+
+    pub fn to_ics(&self) -> String {
+        let mut out = String::new();
+        let stamp = self.timestamp.format("%Y%m%dT%H%M%SZ").to_string();
+
+        line(&mut out, "BEGIN:VCALENDAR");
+        line(&mut out, "VERSION:2.0");
+        line(&mut out, "PRODID:-//studenvue//StudentVue Calendar//EN");
+        line(&mut out, "CALSCALE:GREGORIAN");
+        line(&mut out, "METHOD:PUBLISH");
+        line(
+            &mut out,
+            &format!("X-WR-CALNAME:{}", escape_text(&self.name)),
+        );
+
+        for event in &self.events {
+            line(&mut out, "BEGIN:VEVENT");
+            line(&mut out, &format!("UID:{}", event.uid));
+            line(&mut out, &format!("DTSTAMP:{stamp}"));
+
+            match &event.timing {
+                Timing::AllDay(date) => {
+                    let next = date.succ_opt().unwrap_or(*date);
+                    line(
+                        &mut out,
+                        &format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+                    );
+                    line(
+                        &mut out,
+                        &format!("DTEND;VALUE=DATE:{}", next.format("%Y%m%d")),
+                    );
+                }
+                Timing::Recurring {
+                    first_day,
+                    start,
+                    end,
+                    last_day,
+                    weekdays,
+                    skip_dates,
+                } => {
+                    line(
+                        &mut out,
+                        &format!("DTSTART:{}", local_time(*first_day, *start)),
+                    );
+                    line(&mut out, &format!("DTEND:{}", local_time(*first_day, *end)));
+                    let days: Vec<&str> = weekdays.iter().map(|day| weekday_code(*day)).collect();
+                    line(
+                        &mut out,
+                        &format!(
+                            "RRULE:FREQ=WEEKLY;BYDAY={};UNTIL={}",
+                            days.join(","),
+                            local_time(*last_day, *end)
+                        ),
+                    );
+                    for skip in skip_dates {
+                        line(&mut out, &format!("EXDATE:{}", local_time(*skip, *start)));
+                    }
+                }
+            }
+
+            line(
+                &mut out,
+                &format!("SUMMARY:{}", escape_text(&event.summary)),
+            );
+            if !event.description.is_empty() {
+                line(
+                    &mut out,
+                    &format!("DESCRIPTION:{}", escape_text(&event.description)),
+                );
+            }
+            if !event.location.is_empty() {
+                line(
+                    &mut out,
+                    &format!("LOCATION:{}", escape_text(&event.location)),
+                );
+            }
+            if !event.url.is_empty() {
+                line(&mut out, &format!("URL:{}", event.url));
+            }
+            line(&mut out, "END:VEVENT");
+        }
+
+        line(&mut out, "END:VCALENDAR");
+        out
+    }
+
+    fn push(&mut self, event: IcsEvent) {
+        if self.uids.insert(event.uid.clone()) {
+            self.events.push(event);
+        }
+    }
+}
+
+fn assignment_uid(gradebook_id: &str) -> String {
+    format!("assignment-{gradebook_id}@studentvue")
+}
+
+fn local_time(date: NaiveDate, time: NaiveTime) -> String {
+    date.and_time(time).format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Writes a content line, folding it at 75 octets as RFC 5545 requires.
+fn line(out: &mut String, content: &str) {
+    let mut width = 0;
+    for c in content.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
@@ -1,7 +1,30 @@
+pub mod analytics;
+pub mod archive;
+pub mod assignments;
+pub mod attendance;
+pub mod calendar;
 pub mod client;
+pub mod dates;
+pub mod diff;
+pub mod document;
 pub mod error;
+pub mod gpa;
+pub mod ics;
+pub mod matching;
 pub mod models;
+pub mod render;
+pub mod snapshot;
 pub mod soap;
+pub mod student;
+pub mod trends;
+pub mod whatif;
 
+pub use analytics::AttendanceAnalytics;
+pub use attendance::AttendanceCodes;
 pub use client::StudentVueClient;
 pub use error::{Error, Result};
+pub use gpa::GpaCalculator;
+pub use snapshot::Snapshot;
+pub use student::Student;
+pub use trends::GradeTrendAnalysis;
+pub use whatif::GradeSimulator;
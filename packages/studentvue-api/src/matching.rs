@@ -0,0 +1,23 @@
+//! Loose comparisons for matching the same class across endpoints and
+//! snapshots, which spell titles and periods slightly differently.
+
+/// Compares names ignoring case and surrounding whitespace. A blank name never
+/// matches anything.
+pub fn same_text(a: &str, b: &str) -> bool {
+    !a.trim().is_empty() && a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// Compares period numbers ignoring leading zeros and case, so `02` matches
+/// `2` and `0` matches `00`. A blank period never matches anything.
+pub fn same_period(a: &str, b: &str) -> bool {
+    let normalize = |period: &str| {
+        let period = period.trim();
+        let trimmed = period.trim_start_matches('0');
+        if trimmed.is_empty() && !period.is_empty() {
+            "0".to_string()
+        } else {
+            trimmed.to_ascii_lowercase()
+        }
+    };
+    !a.trim().is_empty() && normalize(a) == normalize(b)
+}
@@ -1,3 +1,6 @@
+use crate::dates::parse_date;
+use crate::render;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,12 +14,58 @@ pub struct DistrictInfo {
 pub struct Message {
     pub id: String,
     pub subject: String,
+    #[serde(default)]
+    pub subject_no_html: String,
     pub content: String,
     pub from: String,
+    #[serde(default)]
+    pub sender: MessageSender,
     pub date: String,
     pub read: bool,
     pub deletable: bool,
     pub message_type: String,
+    #[serde(default)]
+    pub module_name: String,
+    #[serde(default)]
+    pub attachments: Vec<MessageAttachment>,
+}
+
+impl Message {
+    pub fn content_text(&self) -> String {
+        render::html_to_text(&self.content)
+    }
+
+    pub fn content_markdown(&self) -> String {
+        render::html_to_markdown(&self.content)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageSender {
+    pub name: String,
+    pub person_gu: String,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageAttachment {
+    pub attachment_gu: String,
+    pub document_name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MessageAction {
+    MarkRead,
+    MarkUnread,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageActionResult {
+    pub message_id: String,
+    pub action: MessageAction,
+    pub success: bool,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,12 +74,100 @@ pub struct CalendarEvent {
     pub title: String,
     pub icon: String,
     pub day_type: String,
+    #[serde(default)]
+    pub start_time: String,
+    #[serde(default)]
+    pub link: String,
+    #[serde(default)]
+    pub kind: CalendarEventKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CalendarEventKind {
+    Assignment {
+        assignment_gu: String,
+        gradebook_gu: String,
+        view_type: String,
+    },
+    Holiday,
+    SchoolEvent,
+    Other(String),
+}
+
+impl Default for CalendarEventKind {
+    fn default() -> Self {
+        CalendarEventKind::Other(String::new())
+    }
+}
+
+impl CalendarEventKind {
+    pub fn classify(
+        day_type: &str,
+        title: &str,
+        assignment_gu: String,
+        gradebook_gu: String,
+        view_type: String,
+    ) -> Self {
+        let day_type_lower = day_type.trim().to_lowercase();
+        let title_lower = title.to_lowercase();
+
+        if day_type_lower == "assignment" || !assignment_gu.is_empty() {
+            CalendarEventKind::Assignment {
+                assignment_gu,
+                gradebook_gu,
+                view_type,
+            }
+        } else if day_type_lower.contains("holiday")
+            || day_type_lower.contains("no school")
+            || title_lower.contains("no school")
+        {
+            CalendarEventKind::Holiday
+        } else if day_type_lower.is_empty() || day_type_lower == "regular" {
+            CalendarEventKind::SchoolEvent
+        } else {
+            CalendarEventKind::Other(day_type.to_string())
+        }
+    }
+}
+
+impl CalendarEvent {
+    pub fn is_school_closed(&self) -> bool {
+        self.kind == CalendarEventKind::Holiday
+    }
+
+    pub fn assignment_gu(&self) -> Option<&str> {
+        match &self.kind {
+            CalendarEventKind::Assignment { assignment_gu, .. } if !assignment_gu.is_empty() => {
+                Some(assignment_gu)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarListing {
+    pub school_begin_date: String,
+    pub school_end_date: String,
+    pub month_begin_date: String,
+    pub events: Vec<CalendarEvent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub school_begin_date: Option<NaiveDate>,
+    pub school_end_date: Option<NaiveDate>,
+    pub events: Vec<CalendarEvent>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Absence {
     pub date: String,
     pub reason: String,
+    #[serde(default)]
+    pub kind: AttendanceKind,
     pub note: String,
     pub periods: Vec<AbsencePeriod>,
 }
@@ -40,12 +177,141 @@ pub struct AbsencePeriod {
     pub number: String,
     pub name: String,
     pub reason: String,
+    #[serde(default)]
+    pub kind: AttendanceKind,
     pub course: String,
     pub staff: String,
     pub staff_email: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum AttendanceKind {
+    Excused,
+    Unexcused,
+    Tardy,
+    UnexcusedTardy,
+    Activity,
+    #[default]
+    Unknown,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttendanceReport {
+    pub attendance_type: String,
+    pub school_name: String,
+    pub period_count: u32,
+    pub absences: Vec<Absence>,
+    pub total_excused: PeriodTotals,
+    pub total_tardies: PeriodTotals,
+    pub total_unexcused: PeriodTotals,
+    pub total_activities: PeriodTotals,
+    pub total_unexcused_tardies: PeriodTotals,
+}
+
+impl AttendanceReport {
+    pub fn totals_for(&self, kind: AttendanceKind) -> Option<&PeriodTotals> {
+        match kind {
+            AttendanceKind::Excused => Some(&self.total_excused),
+            AttendanceKind::Unexcused => Some(&self.total_unexcused),
+            AttendanceKind::Tardy => Some(&self.total_tardies),
+            AttendanceKind::UnexcusedTardy => Some(&self.total_unexcused_tardies),
+            AttendanceKind::Activity => Some(&self.total_activities),
+            AttendanceKind::Unknown => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PeriodTotals {
+    pub periods: Vec<PeriodTotal>,
+}
+
+impl PeriodTotals {
+    pub fn sum(&self) -> u32 {
+        self.periods.iter().map(|p| p.total).sum()
+    }
+
+    pub fn for_period(&self, number: u32) -> u32 {
+        self.periods
+            .iter()
+            .filter(|p| p.number == number)
+            .map(|p| p.total)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodTotal {
+    pub number: u32,
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Gradebook {
+    pub reporting_periods: Vec<ReportPeriod>,
+    pub reporting_period: Option<ReportPeriod>,
+    pub courses: Vec<Course>,
+}
+
+/// A grading period from the gradebook. `index` is the value `get_gradebook`
+/// takes; the currently displayed period may omit it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportPeriod {
+    pub index: Option<usize>,
+    pub name: String,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+}
+
+impl ReportPeriod {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        match (self.start_date, self.end_date) {
+            (Some(start), Some(end)) => start <= date && date <= end,
+            _ => false,
+        }
+    }
+}
+
+impl Gradebook {
+    /// The reporting period whose dates include `date`.
+    pub fn reporting_period_for(&self, date: NaiveDate) -> Option<&ReportPeriod> {
+        self.reporting_periods
+            .iter()
+            .find(|period| period.contains(date))
+    }
+
+    /// The reporting period in effect on `date`. Between periods (over a
+    /// break) this is the most recent one that has ended; before the first
+    /// period starts it is the first period.
+    pub fn reporting_period_on(&self, date: NaiveDate) -> Option<&ReportPeriod> {
+        self.reporting_period_for(date)
+            .or_else(|| {
+                self.reporting_periods
+                    .iter()
+                    .filter(|period| period.end_date.is_some_and(|end| end < date))
+                    .max_by_key(|period| period.end_date)
+            })
+            .or_else(|| {
+                self.reporting_periods
+                    .iter()
+                    .filter(|period| period.start_date.is_some())
+                    .min_by_key(|period| period.start_date)
+            })
+    }
+
+    /// Whether this gradebook is the one for the reporting period at `index`.
+    pub fn shows_period(&self, index: usize) -> bool {
+        let Some(shown) = &self.reporting_period else {
+            return false;
+        };
+        self.reporting_periods
+            .iter()
+            .find(|period| period.index == Some(index))
+            .is_some_and(|period| period.name == shown.name)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Course {
     pub period: String,
     pub title: String,
@@ -55,16 +321,109 @@ pub struct Course {
     pub marks: Vec<Mark>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Mark {
     pub mark_name: String,
     pub score: String,
     pub score_raw: Option<String>,
+    #[serde(default)]
+    pub categories: Vec<GradeCategory>,
+    #[serde(default)]
+    pub standards: Vec<StandardMark>,
     pub assignments: Vec<Assignment>,
 }
 
+impl Mark {
+    /// True for marks on a standards-based report card, which carry
+    /// proficiency levels per standard instead of a percentage.
+    pub fn is_standards_based(&self) -> bool {
+        !self.standards.is_empty()
+    }
+
+    /// The assignment categories that make up the mark, without the portal's
+    /// `TOTAL` row.
+    pub fn weighted_categories(&self) -> impl Iterator<Item = &GradeCategory> {
+        self.categories
+            .iter()
+            .filter(|category| !category.is_total())
+    }
+
+    /// The portal's `TOTAL` row from the grade calculation summary.
+    pub fn category_total(&self) -> Option<&GradeCategory> {
+        self.categories.iter().find(|category| category.is_total())
+    }
+
+    /// Recomputes the course percentage from the category weights and points.
+    /// Categories with nothing graded yet are left out and the remaining
+    /// weights are scaled up to cover them, as StudentVue does.
+    pub fn calculated_percent(&self) -> Option<f64> {
+        let (weighted, weights) = self
+            .weighted_categories()
+            .filter_map(|category| Some((category.weight?, category.percent()?)))
+            .fold((0.0, 0.0), |(weighted, weights), (weight, percent)| {
+                (weighted + weight * percent, weights + weight)
+            });
+
+        (weights > 0.0).then(|| weighted / weights)
+    }
+}
+
+/// A standard on a standards-based report card, e.g. "Reads grade-level text
+/// fluently" scored 3 on a 1-4 proficiency scale.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandardMark {
+    pub standard_id: String,
+    pub subject: String,
+    pub description: String,
+    pub proficiency: Option<f64>,
+    pub proficiency_label: String,
+    pub scale_min: Option<f64>,
+    pub scale_max: Option<f64>,
+}
+
+impl StandardMark {
+    /// Where the proficiency sits on its scale, from 0.0 to 1.0.
+    pub fn scale_position(&self) -> Option<f64> {
+        let proficiency = self.proficiency?;
+        let max = self.scale_max?;
+        let min = self.scale_min.unwrap_or(0.0);
+        (max > min).then(|| (proficiency - min) / (max - min))
+    }
+
+    pub fn is_graded(&self) -> bool {
+        self.proficiency.is_some() || !self.proficiency_label.trim().is_empty()
+    }
+}
+
+/// One row of a mark's `GradeCalculationSummary`. `weight` and
+/// `weighted_percent` are percentages, e.g. `20.0` for `20%`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradeCategory {
+    pub name: String,
+    pub weight: Option<f64>,
+    pub points: Option<f64>,
+    pub points_possible: Option<f64>,
+    pub weighted_percent: Option<f64>,
+    pub calculated_mark: String,
+}
+
+impl GradeCategory {
+    pub fn is_total(&self) -> bool {
+        self.name.eq_ignore_ascii_case("total")
+    }
+
+    pub fn percent(&self) -> Option<f64> {
+        match (self.points, self.points_possible) {
+            (Some(points), Some(possible)) if possible > 0.0 => Some(points / possible * 100.0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Assignment {
+    #[serde(default)]
+    pub gradebook_id: String,
     pub measure: String,
     pub assignment_type: String,
     pub date: String,
@@ -73,6 +432,75 @@ pub struct Assignment {
     pub score_type: String,
     pub points: String,
     pub notes: String,
+    #[serde(default)]
+    pub resources: Vec<AssignmentResource>,
+    /// Standards this assignment is scored against, on standards-based
+    /// report cards.
+    #[serde(default)]
+    pub standards: Vec<StandardMark>,
+}
+
+/// A file or link a teacher attached to an assignment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssignmentResource {
+    pub resource_id: String,
+    pub resource_name: String,
+    pub resource_type: String,
+    pub file_name: String,
+    pub url: String,
+    pub description: String,
+}
+
+impl AssignmentResource {
+    pub fn is_file(&self) -> bool {
+        self.resource_type.eq_ignore_ascii_case("file")
+            || (self.url.is_empty() && !self.file_name.is_empty())
+    }
+}
+
+impl Assignment {
+    pub fn file_resources(&self) -> impl Iterator<Item = &AssignmentResource> {
+        self.resources.iter().filter(|resource| resource.is_file())
+    }
+
+    /// Points earned, read from `points` (`"8.00 / 10.0000"`) or from `score`
+    /// (`"8 out of 10.0000"`). `None` while the assignment is ungraded.
+    pub fn points_earned(&self) -> Option<f64> {
+        self.points_pair().0
+    }
+
+    /// Points possible, available for ungraded assignments too
+    /// (`"10 Points Possible"`).
+    pub fn points_possible(&self) -> Option<f64> {
+        self.points_pair().1
+    }
+
+    pub fn is_graded(&self) -> bool {
+        self.points_earned().is_some()
+    }
+
+    pub fn due(&self) -> Option<NaiveDate> {
+        parse_date(&self.due_date)
+    }
+
+    fn points_pair(&self) -> (Option<f64>, Option<f64>) {
+        if let Some((earned, possible)) = self.points.split_once('/') {
+            return (number(earned), number(possible));
+        }
+        if let Some((earned, possible)) = self.score.split_once(" out of ") {
+            return (number(earned), number(possible));
+        }
+        let possible = self
+            .points
+            .to_ascii_lowercase()
+            .strip_suffix("points possible")
+            .and_then(number);
+        (None, possible)
+    }
+}
+
+fn number(text: &str) -> Option<f64> {
+    text.trim().parse().ok()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +538,18 @@ pub struct SchoolInfo {
     pub zip: String,
     pub phone: String,
     pub url: String,
+    #[serde(default)]
+    pub staff: Vec<StaffMember>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffMember {
+    pub staff_gu: String,
+    pub name: String,
+    pub email: String,
+    pub title: String,
+    pub phone: String,
+    pub extension: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -145,3 +585,75 @@ pub struct HealthImmunization {
     pub required_doses: String,
     pub dates: Vec<String>,
 }
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CourseHistory {
+    pub terms: Vec<HistoryTerm>,
+    pub gpas: Vec<ReportedGpa>,
+}
+
+impl CourseHistory {
+    pub fn courses(&self) -> impl Iterator<Item = (&HistoryTerm, &HistoryCourse)> {
+        self.terms
+            .iter()
+            .flat_map(|term| term.courses.iter().map(move |course| (term, course)))
+    }
+
+    pub fn credits_attempted(&self) -> f64 {
+        self.terms.iter().map(HistoryTerm::credits_attempted).sum()
+    }
+
+    pub fn credits_earned(&self) -> f64 {
+        self.terms.iter().map(HistoryTerm::credits_earned).sum()
+    }
+
+    /// Looks up a district-reported GPA by name, ignoring case.
+    pub fn gpa(&self, name: &str) -> Option<&ReportedGpa> {
+        self.gpas
+            .iter()
+            .find(|gpa| gpa.name.eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryTerm {
+    pub school_name: String,
+    pub school_year: String,
+    pub grade: String,
+    pub term_name: String,
+    pub courses: Vec<HistoryCourse>,
+}
+
+impl HistoryTerm {
+    pub fn credits_attempted(&self) -> f64 {
+        self.courses
+            .iter()
+            .filter_map(|course| course.credits_attempted)
+            .sum()
+    }
+
+    pub fn credits_earned(&self) -> f64 {
+        self.courses
+            .iter()
+            .filter_map(|course| course.credits_earned)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryCourse {
+    pub course_id: String,
+    pub course_title: String,
+    pub mark: String,
+    pub credits_attempted: Option<f64>,
+    pub credits_earned: Option<f64>,
+    pub course_type: String,
+    pub subject_area: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportedGpa {
+    pub name: String,
+    pub value: Option<f64>,
+    pub school_year: String,
+}
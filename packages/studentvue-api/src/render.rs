@@ -0,0 +1,353 @@
+/// Converts a message body written in HTML into readable plain text.
+///
+/// Scripts, styles and comments are dropped, entities are decoded, and links
+/// are kept as `text (url)` when the text does not already show the URL.
+pub fn html_to_text(html: &str) -> String {
+    render(html, Format::Text)
+}
+
+/// Converts a message body written in HTML into Markdown, keeping headings,
+/// emphasis, lists and links.
+pub fn html_to_markdown(html: &str) -> String {
+    render(html, Format::Markdown)
+}
+
+/// Decodes named and numeric HTML character references. Unknown references
+/// are left untouched.
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end + 1]).map(|c| (c, end + 2)));
+
+        match entity {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+
+    let c = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "ndash" => '\u{2013}',
+        "mdash" => '\u{2014}',
+        "lsquo" => '\u{2018}',
+        "rsquo" => '\u{2019}',
+        "ldquo" => '\u{201C}',
+        "rdquo" => '\u{201D}',
+        "hellip" => '\u{2026}',
+        "bull" => '\u{2022}',
+        "middot" => '\u{00B7}',
+        "copy" => '\u{00A9}',
+        "reg" => '\u{00AE}',
+        "trade" => '\u{2122}',
+        "deg" => '\u{00B0}',
+        _ => return None,
+    };
+    Some(c)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Markdown,
+}
+
+fn render(html: &str, format: Format) -> String {
+    // Message bodies often arrive entity-encoded a second time, so the markup
+    // itself shows up as `&lt;p&gt;`.
+    let html = if !html.contains('<') && html.contains("&lt;") {
+        decode_entities(html)
+    } else {
+        html.to_string()
+    };
+
+    let mut renderer = Renderer::new(format);
+    let mut rest = html.as_str();
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment
+                .find("-->")
+                .map(|end| &comment[end + 3..])
+                .unwrap_or("");
+        } else if is_tag_start(rest) {
+            let end = tag_end(rest);
+            renderer.tag(&rest[1..end]);
+            rest = rest.get(end + 1..).unwrap_or("");
+        } else {
+            let first = rest.chars().next().map_or(0, char::len_utf8);
+            let end = rest[first..]
+                .find('<')
+                .map(|i| i + first)
+                .unwrap_or(rest.len());
+            renderer.text(&rest[..end]);
+            rest = &rest[end..];
+        }
+    }
+
+    renderer.finish()
+}
+
+fn is_tag_start(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next() == Some('<')
+        && matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '/' || c == '!')
+}
+
+fn tag_end(text: &str) -> usize {
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return i,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search = 0;
+
+    while let Some(found) = lower[search..].find(name) {
+        let start = search + found;
+        search = start + name.len();
+
+        let preceded_by_space = lower[..start].ends_with(|c: char| c.is_whitespace());
+        let rest = lower[search..].trim_start();
+        if !preceded_by_space || !rest.starts_with('=') {
+            continue;
+        }
+
+        let offset = tag.len() - rest.len() + 1;
+        let value = tag[offset..].trim_start();
+        let value = match value.chars().next() {
+            Some(q @ ('"' | '\'')) => value[1..].split(q).next().unwrap_or(""),
+            _ => value.split_whitespace().next().unwrap_or(""),
+        };
+        return Some(decode_entities(value.trim()));
+    }
+
+    None
+}
+
+struct Renderer {
+    format: Format,
+    out: String,
+    pending_space: bool,
+    skip_depth: usize,
+    links: Vec<(usize, Option<String>)>,
+    lists: Vec<Option<usize>>,
+}
+
+impl Renderer {
+    fn new(format: Format) -> Self {
+        Self {
+            format,
+            out: String::new(),
+            pending_space: false,
+            skip_depth: 0,
+            links: Vec::new(),
+            lists: Vec::new(),
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.skip_depth > 0 {
+            return;
+        }
+
+        for c in decode_entities(text).chars() {
+            if c.is_whitespace() {
+                self.pending_space = true;
+            } else {
+                self.push_word_char(c);
+            }
+        }
+    }
+
+    fn push_word_char(&mut self, c: char) {
+        if self.pending_space && !self.out.is_empty() && !self.out.ends_with([' ', '\n']) {
+            self.out.push(' ');
+        }
+        self.pending_space = false;
+        self.out.push(c);
+    }
+
+    fn push_str(&mut self, text: &str) {
+        for c in text.chars() {
+            self.push_word_char(c);
+        }
+    }
+
+    fn newlines(&mut self, count: usize) {
+        self.pending_space = false;
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+        if self.out.is_empty() {
+            return;
+        }
+
+        let existing = self.out.len() - self.out.trim_end_matches('\n').len();
+        for _ in existing..count {
+            self.out.push('\n');
+        }
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let tag = tag.trim_end_matches('/').trim();
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+
+        match name.as_str() {
+            "script" | "style" | "head" | "title" => {
+                if closing {
+                    self.skip_depth = self.skip_depth.saturating_sub(1);
+                } else {
+                    self.skip_depth += 1;
+                }
+            }
+            _ if self.skip_depth > 0 => {}
+            "br" => self.newlines(1),
+            "p" | "blockquote" | "table" | "hr" => self.newlines(2),
+            "div" | "tr" | "section" | "article" => self.newlines(1),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.newlines(2);
+                if !closing && self.format == Format::Markdown {
+                    let level = name[1..].parse().unwrap_or(1);
+                    self.out.push_str(&"#".repeat(level));
+                    self.out.push(' ');
+                }
+            }
+            "ul" | "ol" => {
+                if closing {
+                    self.lists.pop();
+                } else {
+                    self.lists.push((name == "ol").then_some(0));
+                }
+                self.newlines(if self.lists.is_empty() { 2 } else { 1 });
+            }
+            "li" if !closing => {
+                self.newlines(1);
+                let depth = self.lists.len().saturating_sub(1);
+                self.out.push_str(&"  ".repeat(depth));
+                match self.lists.last_mut() {
+                    Some(Some(counter)) => {
+                        *counter += 1;
+                        let marker = format!("{counter}. ");
+                        self.out.push_str(&marker);
+                    }
+                    _ => self.out.push_str("- "),
+                }
+            }
+            "td" | "th" if closing => self.pending_space = true,
+            "b" | "strong" if self.format == Format::Markdown => self.emphasis("**", closing),
+            "i" | "em" if self.format == Format::Markdown => self.emphasis("_", closing),
+            "a" => {
+                if closing {
+                    self.close_link();
+                } else {
+                    let href = attribute(tag, "href")
+                        .filter(|href| !href.is_empty())
+                        .filter(|href| !href.to_ascii_lowercase().starts_with("javascript:"));
+                    if self.pending_space
+                        && !self.out.ends_with([' ', '\n'])
+                        && !self.out.is_empty()
+                    {
+                        self.out.push(' ');
+                        self.pending_space = false;
+                    }
+                    self.links.push((self.out.len(), href));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn emphasis(&mut self, marker: &str, closing: bool) {
+        if closing {
+            self.out.push_str(marker);
+        } else {
+            self.push_str(marker);
+        }
+    }
+
+    fn close_link(&mut self) {
+        let Some((start, Some(href))) = self.links.pop() else {
+            return;
+        };
+
+        let text = self.out[start..].trim().to_string();
+        let target = href.strip_prefix("mailto:").unwrap_or(&href);
+        match self.format {
+            Format::Markdown => {
+                let label = if text.is_empty() { href.clone() } else { text };
+                self.out.truncate(start);
+                self.out.push_str(&format!("[{label}]({href})"));
+            }
+            Format::Text if text.is_empty() => self.out.push_str(&href),
+            Format::Text if !text.contains(target) => {
+                self.out.push_str(&format!(" ({href})"));
+            }
+            Format::Text => {}
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut result = String::new();
+        let mut blank_lines = 0;
+
+        for line in self.out.lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                blank_lines += 1;
+                continue;
+            }
+            if !result.is_empty() {
+                result.push_str(if blank_lines > 0 { "\n\n" } else { "\n" });
+            }
+            result.push_str(line);
+            blank_lines = 0;
+        }
+
+        result
+    }
+}
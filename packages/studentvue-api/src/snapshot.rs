@@ -0,0 +1,261 @@
+use crate::diff::{diff, Change};
+use crate::error::Result;
+use crate::models::{
+    AttendanceReport, CalendarListing, ClassSchedule, Document, Gradebook, HealthImmunization,
+    Message, ReportCard, SchoolInfo, StudentInfo,
+};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_SNAPSHOT_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotEndpoint {
+    StudentInfo,
+    SchoolInfo,
+    Schedule,
+    Gradebook,
+    Attendance,
+    Messages,
+    Calendar,
+    Documents,
+    ReportCards,
+    Health,
+}
+
+/// An endpoint that could not be fetched. `reporting_period` is set for the
+/// gradebook of a specific reporting period; a gradebook error without one
+/// means the list of reporting periods could not be fetched, so no period's
+/// gradebook is in the snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotError {
+    pub endpoint: SnapshotEndpoint,
+    pub reporting_period: Option<usize>,
+    pub error: String,
+}
+
+/// Everything the portal knows about a student at one point in time.
+///
+/// Each field is `None` when its endpoint failed, with the reason recorded in
+/// `errors`, so a failed fetch is never mistaken for an empty one. Gradebooks
+/// are in reporting period order; a failed period is left out. The calendar
+/// covers only the month the snapshot was taken in; use
+/// `StudentVueClient::get_calendar_range` for more.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub taken_at: DateTime<Local>,
+    pub student_info: Option<StudentInfo>,
+    pub school_info: Option<SchoolInfo>,
+    pub schedule: Option<Vec<ClassSchedule>>,
+    pub gradebooks: Vec<Gradebook>,
+    pub attendance: Option<AttendanceReport>,
+    pub messages: Option<Vec<Message>>,
+    pub calendar: Option<CalendarListing>,
+    pub documents: Option<Vec<Document>>,
+    pub report_cards: Option<Vec<ReportCard>>,
+    pub health: Option<Vec<HealthImmunization>>,
+    pub errors: Vec<SnapshotError>,
+}
+
+impl Snapshot {
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn failed(&self, endpoint: SnapshotEndpoint) -> bool {
+        self.errors.iter().any(|error| error.endpoint == endpoint)
+    }
+
+    /// The gradebook for a reporting period, by its index.
+    pub fn gradebook(&self, index: usize) -> Option<&Gradebook> {
+        self.gradebooks
+            .iter()
+            .find(|gradebook| gradebook.shows_period(index))
+    }
+
+    /// What changed since `earlier`, a snapshot of the same student.
+    pub fn changes_since(&self, earlier: &Snapshot) -> Vec<Change> {
+        diff(earlier, self)
+    }
+}
+
+/// One request made while taking a snapshot. `Gradebook(None)` is the
+/// portal's default gradebook, which lists the reporting periods to fetch next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SnapshotRequest {
+    StudentInfo,
+    SchoolInfo,
+    Schedule,
+    Gradebook(Option<usize>),
+    Attendance,
+    Messages,
+    Calendar,
+    Documents,
+    ReportCards,
+    Health,
+}
+
+impl SnapshotRequest {
+    pub(crate) const INITIAL: [SnapshotRequest; 10] = [
+        SnapshotRequest::Gradebook(None),
+        SnapshotRequest::StudentInfo,
+        SnapshotRequest::SchoolInfo,
+        SnapshotRequest::Schedule,
+        SnapshotRequest::Attendance,
+        SnapshotRequest::Messages,
+        SnapshotRequest::Calendar,
+        SnapshotRequest::Documents,
+        SnapshotRequest::ReportCards,
+        SnapshotRequest::Health,
+    ];
+
+    fn endpoint(self) -> SnapshotEndpoint {
+        match self {
+            SnapshotRequest::StudentInfo => SnapshotEndpoint::StudentInfo,
+            SnapshotRequest::SchoolInfo => SnapshotEndpoint::SchoolInfo,
+            SnapshotRequest::Schedule => SnapshotEndpoint::Schedule,
+            SnapshotRequest::Gradebook(_) => SnapshotEndpoint::Gradebook,
+            SnapshotRequest::Attendance => SnapshotEndpoint::Attendance,
+            SnapshotRequest::Messages => SnapshotEndpoint::Messages,
+            SnapshotRequest::Calendar => SnapshotEndpoint::Calendar,
+            SnapshotRequest::Documents => SnapshotEndpoint::Documents,
+            SnapshotRequest::ReportCards => SnapshotEndpoint::ReportCards,
+            SnapshotRequest::Health => SnapshotEndpoint::Health,
+        }
+    }
+}
+
+pub(crate) enum SnapshotPart {
+    StudentInfo(StudentInfo),
+    SchoolInfo(SchoolInfo),
+    Schedule(Vec<ClassSchedule>),
+    Gradebook(Gradebook),
+    Attendance(AttendanceReport),
+    Messages(Vec<Message>),
+    Calendar(CalendarListing),
+    Documents(Vec<Document>),
+    ReportCards(Vec<ReportCard>),
+    Health(Vec<HealthImmunization>),
+}
+
+/// Collects responses as they arrive, in whatever order the requests finish.
+pub(crate) struct SnapshotBuilder {
+    snapshot: Snapshot,
+    default_gradebook: Option<Gradebook>,
+    retried_default_gradebook: bool,
+    gradebooks: Vec<(usize, Gradebook)>,
+}
+
+impl SnapshotBuilder {
+    pub(crate) fn new(taken_at: DateTime<Local>) -> Self {
+        Self {
+            snapshot: Snapshot {
+                taken_at,
+                student_info: None,
+                school_info: None,
+                schedule: None,
+                gradebooks: Vec::new(),
+                attendance: None,
+                messages: None,
+                calendar: None,
+                documents: None,
+                report_cards: None,
+                health: None,
+                errors: Vec::new(),
+            },
+            default_gradebook: None,
+            retried_default_gradebook: false,
+            gradebooks: Vec::new(),
+        }
+    }
+
+    /// Records a response and returns any requests it makes necessary: the
+    /// default gradebook leads to one request per reporting period it lists,
+    /// and is tried a second time if it fails, since without it no period's
+    /// gradebook can be fetched.
+    pub(crate) fn add(
+        &mut self,
+        request: SnapshotRequest,
+        result: Result<SnapshotPart>,
+    ) -> Vec<SnapshotRequest> {
+        let part = match result {
+            Ok(part) => part,
+            Err(_)
+                if request == SnapshotRequest::Gradebook(None)
+                    && !self.retried_default_gradebook =>
+            {
+                self.retried_default_gradebook = true;
+                return vec![request];
+            }
+            Err(e) => {
+                self.snapshot.errors.push(SnapshotError {
+                    endpoint: request.endpoint(),
+                    reporting_period: match request {
+                        SnapshotRequest::Gradebook(index) => index,
+                        _ => None,
+                    },
+                    error: e.to_string(),
+                });
+                return Vec::new();
+            }
+        };
+
+        let snapshot = &mut self.snapshot;
+        match part {
+            SnapshotPart::StudentInfo(info) => snapshot.student_info = Some(info),
+            SnapshotPart::SchoolInfo(school) => snapshot.school_info = Some(school),
+            SnapshotPart::Schedule(schedule) => snapshot.schedule = Some(schedule),
+            SnapshotPart::Attendance(report) => snapshot.attendance = Some(report),
+            SnapshotPart::Messages(messages) => snapshot.messages = Some(messages),
+            SnapshotPart::Calendar(calendar) => snapshot.calendar = Some(calendar),
+            SnapshotPart::Documents(documents) => snapshot.documents = Some(documents),
+            SnapshotPart::ReportCards(report_cards) => snapshot.report_cards = Some(report_cards),
+            SnapshotPart::Health(health) => snapshot.health = Some(health),
+            SnapshotPart::Gradebook(gradebook) => match request {
+                SnapshotRequest::Gradebook(Some(index)) => {
+                    self.gradebooks.push((index, gradebook));
+                }
+                _ => {
+                    let mut follow_up = Vec::new();
+                    for index in gradebook
+                        .reporting_periods
+                        .iter()
+                        .filter_map(|period| period.index)
+                    {
+                        if gradebook.shows_period(index) {
+                            self.gradebooks.push((index, gradebook.clone()));
+                        } else {
+                            follow_up.push(SnapshotRequest::Gradebook(Some(index)));
+                        }
+                    }
+                    self.default_gradebook = Some(gradebook);
+                    return follow_up;
+                }
+            },
+        }
+        Vec::new()
+    }
+
+    pub(crate) fn finish(mut self) -> Snapshot {
+        self.gradebooks.sort_by_key(|(index, _)| *index);
+        self.snapshot.gradebooks = self
+            .gradebooks
+            .into_iter()
+            .map(|(_, gradebook)| gradebook)
+            .collect();
+
+        // A portal that lists no reporting periods only has the default gradebook.
+        if let Some(gradebook) = self.default_gradebook {
+            let indexed = gradebook
+                .reporting_periods
+                .iter()
+                .any(|period| period.index.is_some());
+            if !indexed {
+                self.snapshot.gradebooks.push(gradebook);
+            }
+        }
+
+        self.snapshot
+    }
+}
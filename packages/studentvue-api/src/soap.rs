@@ -111,7 +111,7 @@ impl SoapClient {
         param_parts
     }
 
-    fn escape_xml(text: &str) -> String {
+    pub fn escape_xml(text: &str) -> String {
         text.replace('&', "&amp;")
             .replace('<', "&lt;")
             .replace('>', "&gt;")
@@ -0,0 +1,256 @@
+use crate::matching::{same_period, same_text};
+use crate::models::{
+    AbsencePeriod, Assignment, AttendanceReport, ClassSchedule, Course, SchoolInfo, StaffMember,
+    StudentInfo,
+};
+use serde::{Deserialize, Serialize};
+
+/// A period-level absence recorded against a course.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourseAbsence {
+    pub date: String,
+    pub period: AbsencePeriod,
+}
+
+/// Everything known about one class: its gradebook entry, its schedule entry,
+/// the teacher's staff directory listing and the absences recorded in it. Any
+/// part may be missing when an endpoint does not mention the class.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudentCourse {
+    pub period: String,
+    pub title: String,
+    pub gradebook: Option<Course>,
+    pub schedule: Option<ClassSchedule>,
+    pub teacher: Option<StaffMember>,
+    pub absences: Vec<CourseAbsence>,
+}
+
+impl StudentCourse {
+    pub fn teacher_name(&self) -> &str {
+        self.gradebook
+            .as_ref()
+            .map(|course| course.staff.as_str())
+            .filter(|name| !name.is_empty())
+            .or_else(|| self.schedule.as_ref().map(|entry| entry.teacher.as_str()))
+            .filter(|name| !name.is_empty())
+            .or_else(|| self.teacher.as_ref().map(|staff| staff.name.as_str()))
+            .unwrap_or("")
+    }
+
+    pub fn teacher_email(&self) -> &str {
+        self.teacher
+            .as_ref()
+            .map(|staff| staff.email.as_str())
+            .filter(|email| !email.is_empty())
+            .or_else(|| {
+                self.gradebook
+                    .as_ref()
+                    .map(|course| course.staff_email.as_str())
+            })
+            .filter(|email| !email.is_empty())
+            .or_else(|| {
+                self.schedule
+                    .as_ref()
+                    .map(|entry| entry.teacher_email.as_str())
+            })
+            .unwrap_or("")
+    }
+
+    pub fn room(&self) -> &str {
+        self.schedule
+            .as_ref()
+            .map(|entry| entry.room_name.as_str())
+            .filter(|room| !room.is_empty())
+            .or_else(|| self.gradebook.as_ref().map(|course| course.room.as_str()))
+            .unwrap_or("")
+    }
+
+    /// The course's assignments across all of its marks, each listed once.
+    pub fn assignments(&self) -> Vec<&Assignment> {
+        let mut assignments: Vec<&Assignment> = Vec::new();
+        for mark in self.gradebook.iter().flat_map(|course| &course.marks) {
+            for assignment in &mark.assignments {
+                let seen = assignments.iter().any(|existing| {
+                    if assignment.gradebook_id.is_empty() {
+                        existing.measure == assignment.measure
+                            && existing.due_date == assignment.due_date
+                    } else {
+                        existing.gradebook_id == assignment.gradebook_id
+                    }
+                });
+                if !seen {
+                    assignments.push(assignment);
+                }
+            }
+        }
+        assignments
+    }
+}
+
+/// A student's gradebook, schedule, attendance and staff directory joined
+/// course by course.
+///
+/// Gradebook and schedule entries are matched by period and title, falling
+/// back to title alone. Teachers are found in the school's staff list by email,
+/// then by name regardless of "Last, First" ordering. Absences are attached by
+/// period number and course title.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Student {
+    pub info: StudentInfo,
+    pub school: SchoolInfo,
+    pub attendance: AttendanceReport,
+    pub courses: Vec<StudentCourse>,
+}
+
+impl Student {
+    pub fn link(
+        info: StudentInfo,
+        school: SchoolInfo,
+        gradebook: Vec<Course>,
+        schedule: Vec<ClassSchedule>,
+        attendance: AttendanceReport,
+    ) -> Self {
+        let mut schedule: Vec<Option<ClassSchedule>> = schedule.into_iter().map(Some).collect();
+        let mut courses: Vec<StudentCourse> = gradebook
+            .into_iter()
+            .map(|course| {
+                let entry = take_schedule_entry(&mut schedule, &course);
+                StudentCourse {
+                    period: course.period.clone(),
+                    title: course.title.clone(),
+                    gradebook: Some(course),
+                    schedule: entry,
+                    teacher: None,
+                    absences: Vec::new(),
+                }
+            })
+            .collect();
+        courses.extend(schedule.into_iter().flatten().map(|entry| StudentCourse {
+            period: entry.period.clone(),
+            title: entry.course_title.clone(),
+            gradebook: None,
+            schedule: Some(entry),
+            teacher: None,
+            absences: Vec::new(),
+        }));
+
+        for course in &mut courses {
+            course.teacher = find_staff(&school.staff, course).cloned();
+        }
+
+        for absence in &attendance.absences {
+            for period in &absence.periods {
+                let index = courses
+                    .iter()
+                    .position(|course| {
+                        same_period(&course.period, &period.number)
+                            && same_text(&course.title, &period.course)
+                    })
+                    .or_else(|| {
+                        courses
+                            .iter()
+                            .position(|course| same_text(&course.title, &period.course))
+                    })
+                    .or_else(|| {
+                        // A period alone only identifies the course when the
+                        // portal left the course name out; a named course we
+                        // do not know stays unlinked.
+                        if !period.course.trim().is_empty() {
+                            return None;
+                        }
+                        courses
+                            .iter()
+                            .position(|course| same_period(&course.period, &period.number))
+                    });
+                if let Some(index) = index {
+                    courses[index].absences.push(CourseAbsence {
+                        date: absence.date.clone(),
+                        period: period.clone(),
+                    });
+                }
+            }
+        }
+
+        Self {
+            info,
+            school,
+            attendance,
+            courses,
+        }
+    }
+
+    /// Looks up a course by title, ignoring case.
+    pub fn course(&self, title: &str) -> Option<&StudentCourse> {
+        self.courses.iter().find(|course| {
+            same_text(&course.title, title)
+                || course
+                    .schedule
+                    .as_ref()
+                    .is_some_and(|entry| same_text(&entry.course_title, title))
+        })
+    }
+
+    pub fn course_for_period(&self, period: &str) -> Option<&StudentCourse> {
+        self.courses
+            .iter()
+            .find(|course| same_period(&course.period, period))
+    }
+}
+
+fn take_schedule_entry(
+    schedule: &mut [Option<ClassSchedule>],
+    course: &Course,
+) -> Option<ClassSchedule> {
+    let matches = |strict: bool| {
+        move |entry: &Option<ClassSchedule>| {
+            entry.as_ref().is_some_and(|entry| {
+                same_text(&entry.course_title, &course.title)
+                    && (!strict || same_period(&entry.period, &course.period))
+            })
+        }
+    };
+
+    let index = schedule
+        .iter()
+        .position(matches(true))
+        .or_else(|| schedule.iter().position(matches(false)))?;
+    schedule[index].take()
+}
+
+fn find_staff<'a>(staff: &'a [StaffMember], course: &StudentCourse) -> Option<&'a StaffMember> {
+    let emails = [
+        course.gradebook.as_ref().map(|c| c.staff_email.as_str()),
+        course.schedule.as_ref().map(|s| s.teacher_email.as_str()),
+    ];
+    let names = [
+        course.gradebook.as_ref().map(|c| c.staff.as_str()),
+        course.schedule.as_ref().map(|s| s.teacher.as_str()),
+    ];
+
+    emails
+        .iter()
+        .flatten()
+        .filter(|email| !email.is_empty())
+        .find_map(|email| staff.iter().find(|member| same_text(&member.email, email)))
+        .or_else(|| {
+            names
+                .iter()
+                .flatten()
+                .filter(|name| !name.is_empty())
+                .find_map(|name| {
+                    let key = name_key(name);
+                    staff.iter().find(|member| name_key(&member.name) == key)
+                })
+        })
+}
+
+/// Lowercased name parts in sorted order, so "Lee, Dana" and "Dana Lee" agree.
+fn name_key(name: &str) -> Vec<String> {
+    let mut parts: Vec<String> = name
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    parts.sort();
+    parts
+}
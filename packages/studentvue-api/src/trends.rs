@@ -0,0 +1,214 @@
+use crate::matching::same_text;
+use crate::models::{Course, Gradebook, Mark};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendPoint {
+    pub period_name: String,
+    pub score: Option<f64>,
+    pub mark: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreDrop {
+    pub from_period: String,
+    pub to_period: String,
+    pub from_score: f64,
+    pub to_score: f64,
+}
+
+impl ScoreDrop {
+    pub fn change(&self) -> f64 {
+        self.to_score - self.from_score
+    }
+}
+
+/// One course's scores across reporting periods, in period order. Periods the
+/// course did not appear in have no score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourseTrend {
+    pub title: String,
+    pub period: String,
+    pub teacher: String,
+    pub points: Vec<TrendPoint>,
+    pub change: Option<f64>,
+    pub drops: Vec<ScoreDrop>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradeTrends {
+    pub periods: Vec<String>,
+    pub courses: Vec<CourseTrend>,
+}
+
+impl GradeTrends {
+    /// Courses whose score went up, biggest gain first.
+    pub fn improved(&self) -> Vec<&CourseTrend> {
+        let mut courses: Vec<&CourseTrend> = self
+            .courses
+            .iter()
+            .filter(|course| course.change.is_some_and(|change| change > 0.0))
+            .collect();
+        courses.sort_by(|a, b| b.change.unwrap_or(0.0).total_cmp(&a.change.unwrap_or(0.0)));
+        courses
+    }
+
+    /// Courses whose score went down, biggest loss first.
+    pub fn declined(&self) -> Vec<&CourseTrend> {
+        let mut courses: Vec<&CourseTrend> = self
+            .courses
+            .iter()
+            .filter(|course| course.change.is_some_and(|change| change < 0.0))
+            .collect();
+        courses.sort_by(|a, b| a.change.unwrap_or(0.0).total_cmp(&b.change.unwrap_or(0.0)));
+        courses
+    }
+
+    pub fn with_drops(&self) -> impl Iterator<Item = &CourseTrend> {
+        self.courses
+            .iter()
+            .filter(|course| !course.drops.is_empty())
+    }
+}
+
+/// Lines up courses across the gradebooks of several reporting periods and
+/// tracks how each course's score moves.
+///
+/// Courses are matched by title together with teacher or period, then by
+/// title alone, then by period and teacher for a course renamed between
+/// periods. A course's score is the first of its marks with a numeric score,
+/// falling back to the percentage recomputed from category weights. A drop is
+/// flagged when the score falls by at least `drop_threshold` percentage points
+/// from one scored period to the next.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradeTrendAnalysis {
+    pub drop_threshold: f64,
+}
+
+impl Default for GradeTrendAnalysis {
+    fn default() -> Self {
+        Self {
+            drop_threshold: 5.0,
+        }
+    }
+}
+
+impl GradeTrendAnalysis {
+    pub fn new(drop_threshold: f64) -> Self {
+        Self { drop_threshold }
+    }
+
+    pub fn analyze(&self, gradebooks: &[Gradebook]) -> GradeTrends {
+        let periods: Vec<String> = gradebooks
+            .iter()
+            .enumerate()
+            .map(|(i, gradebook)| period_name(gradebook, i))
+            .collect();
+        let mut courses: Vec<CourseTrend> = Vec::new();
+
+        for (i, gradebook) in gradebooks.iter().enumerate() {
+            let mut claimed = HashSet::new();
+            for course in &gradebook.courses {
+                let index = match find_trend(&courses, course, &claimed) {
+                    Some(index) => index,
+                    None => {
+                        courses.push(CourseTrend {
+                            title: course.title.clone(),
+                            period: course.period.clone(),
+                            teacher: course.staff.clone(),
+                            points: periods
+                                .iter()
+                                .map(|name| TrendPoint {
+                                    period_name: name.clone(),
+                                    score: None,
+                                    mark: String::new(),
+                                })
+                                .collect(),
+                            change: None,
+                            drops: Vec::new(),
+                        });
+                        courses.len() - 1
+                    }
+                };
+                claimed.insert(index);
+
+                let point = &mut courses[index].points[i];
+                if let Some(mark) = scored_mark(course) {
+                    point.score = score_of(mark);
+                    point.mark = mark.score.clone();
+                } else if let Some(mark) = course.marks.first() {
+                    point.mark = mark.score.clone();
+                }
+            }
+        }
+
+        for course in &mut courses {
+            let scored: Vec<(&TrendPoint, f64)> = course
+                .points
+                .iter()
+                .filter_map(|point| Some((point, point.score?)))
+                .collect();
+
+            course.change = match (scored.first(), scored.last()) {
+                (Some((_, first)), Some((_, last))) if scored.len() > 1 => Some(last - first),
+                _ => None,
+            };
+            course.drops = scored
+                .windows(2)
+                .filter(|pair| pair[0].1 - pair[1].1 >= self.drop_threshold)
+                .map(|pair| ScoreDrop {
+                    from_period: pair[0].0.period_name.clone(),
+                    to_period: pair[1].0.period_name.clone(),
+                    from_score: pair[0].1,
+                    to_score: pair[1].1,
+                })
+                .collect();
+        }
+
+        GradeTrends { periods, courses }
+    }
+}
+
+fn period_name(gradebook: &Gradebook, position: usize) -> String {
+    gradebook
+        .reporting_period
+        .as_ref()
+        .map(|period| period.name.clone())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| format!("Period {}", position + 1))
+}
+
+/// Finds the trend a course belongs to, skipping trends already matched by
+/// another course in the same gradebook.
+fn find_trend(trends: &[CourseTrend], course: &Course, claimed: &HashSet<usize>) -> Option<usize> {
+    let find = |matches: &dyn Fn(&CourseTrend) -> bool| {
+        trends
+            .iter()
+            .enumerate()
+            .position(|(i, trend)| !claimed.contains(&i) && matches(trend))
+    };
+
+    find(&|trend| {
+        same_text(&trend.title, &course.title)
+            && (same_text(&trend.teacher, &course.staff)
+                || same_text(&trend.period, &course.period))
+    })
+    .or_else(|| find(&|trend| same_text(&trend.title, &course.title)))
+    .or_else(|| {
+        find(&|trend| {
+            same_text(&trend.period, &course.period) && same_text(&trend.teacher, &course.staff)
+        })
+    })
+}
+
+fn scored_mark(course: &Course) -> Option<&Mark> {
+    course.marks.iter().find(|mark| score_of(mark).is_some())
+}
+
+fn score_of(mark: &Mark) -> Option<f64> {
+    mark.score_raw
+        .as_deref()
+        .and_then(|raw| raw.trim().trim_end_matches('%').parse().ok())
+        .or_else(|| mark.calculated_percent())
+}
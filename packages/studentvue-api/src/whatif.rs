@@ -0,0 +1,246 @@
+use crate::models::Mark;
+use serde::{Deserialize, Serialize};
+
+/// Minimum percentages for each letter grade, checked from the top down.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradeCutoffs {
+    cutoffs: Vec<(String, f64)>,
+}
+
+impl Default for GradeCutoffs {
+    fn default() -> Self {
+        Self::new(&[
+            ("A", 93.0),
+            ("A-", 90.0),
+            ("B+", 87.0),
+            ("B", 83.0),
+            ("B-", 80.0),
+            ("C+", 77.0),
+            ("C", 73.0),
+            ("C-", 70.0),
+            ("D+", 67.0),
+            ("D", 63.0),
+            ("D-", 60.0),
+            ("F", 0.0),
+        ])
+    }
+}
+
+impl GradeCutoffs {
+    pub fn new(cutoffs: &[(&str, f64)]) -> Self {
+        let mut cutoffs: Vec<(String, f64)> = cutoffs
+            .iter()
+            .map(|(letter, minimum)| (letter.to_string(), *minimum))
+            .collect();
+        cutoffs.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Self { cutoffs }
+    }
+
+    pub fn letter_for(&self, percent: f64) -> Option<&str> {
+        self.cutoffs
+            .iter()
+            .find(|(_, minimum)| percent >= *minimum)
+            .map(|(letter, _)| letter.as_str())
+    }
+
+    pub fn minimum_for(&self, letter: &str) -> Option<f64> {
+        self.cutoffs
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(letter.trim()))
+            .map(|(_, minimum)| *minimum)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedAssignment {
+    pub name: String,
+    pub category: String,
+    pub earned: Option<f64>,
+    pub possible: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedGrade {
+    pub percent: Option<f64>,
+    pub letter: Option<String>,
+}
+
+/// The score an assignment needs for the mark to reach a target grade.
+/// `points` can exceed `possible` (extra credit) or be negative (any score
+/// works); `achievable` is true when it falls within `0..=possible`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NeededScore {
+    pub points: f64,
+    pub possible: f64,
+    pub achievable: bool,
+}
+
+/// Recomputes a mark with hypothetical scores.
+///
+/// When the mark's grade calculation summary carries category weights, each
+/// category's percentage is weighted and categories with nothing graded are
+/// left out, the same way the portal does it. Otherwise the grade is total
+/// points earned over total points possible. Assignments in a category the
+/// summary does not list count toward nothing under weighting.
+#[derive(Debug, Clone)]
+pub struct GradeSimulator {
+    weights: Vec<(String, f64)>,
+    assignments: Vec<SimulatedAssignment>,
+    cutoffs: GradeCutoffs,
+}
+
+impl GradeSimulator {
+    pub fn new(mark: &Mark) -> Self {
+        let weights = mark
+            .weighted_categories()
+            .filter_map(|category| Some((category.name.clone(), category.weight?)))
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect();
+        let assignments = mark
+            .assignments
+            .iter()
+            .map(|assignment| SimulatedAssignment {
+                name: assignment.measure.clone(),
+                category: assignment.assignment_type.clone(),
+                earned: assignment.points_earned(),
+                possible: assignment.points_possible(),
+            })
+            .collect();
+
+        Self {
+            weights,
+            assignments,
+            cutoffs: GradeCutoffs::default(),
+        }
+    }
+
+    pub fn with_cutoffs(mut self, cutoffs: GradeCutoffs) -> Self {
+        self.cutoffs = cutoffs;
+        self
+    }
+
+    pub fn assignments(&self) -> &[SimulatedAssignment] {
+        &self.assignments
+    }
+
+    /// Sets the score of an existing assignment, matched by name ignoring
+    /// case. Returns false when no assignment has that name.
+    pub fn set_score(&mut self, name: &str, earned: f64, possible: f64) -> bool {
+        match self.find_mut(name) {
+            Some(assignment) => {
+                assignment.earned = Some(earned);
+                assignment.possible = Some(possible);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds a hypothetical assignment, such as a final exam that has not been
+    /// entered in the gradebook yet.
+    pub fn add_assignment(
+        &mut self,
+        name: &str,
+        category: &str,
+        earned: f64,
+        possible: f64,
+    ) -> &mut Self {
+        self.assignments.push(SimulatedAssignment {
+            name: name.to_string(),
+            category: category.to_string(),
+            earned: Some(earned),
+            possible: Some(possible),
+        });
+        self
+    }
+
+    pub fn grade(&self) -> SimulatedGrade {
+        let percent = self.percent();
+        SimulatedGrade {
+            percent,
+            letter: percent
+                .and_then(|percent| self.cutoffs.letter_for(percent))
+                .map(String::from),
+        }
+    }
+
+    /// Works out what `name` needs to score for the mark to reach `letter`.
+    /// Returns `None` when the letter is not in the cutoffs, the assignment
+    /// does not exist or has no points possible, or its score does not move
+    /// the grade.
+    pub fn score_needed(&self, name: &str, letter: &str) -> Option<NeededScore> {
+        let target = self.cutoffs.minimum_for(letter)?;
+        let index = self.position(name)?;
+        let possible = self.assignments[index].possible.filter(|p| *p > 0.0)?;
+
+        let percent_with = |earned: f64| {
+            let mut trial = self.clone();
+            trial.assignments[index].earned = Some(earned);
+            trial.assignments[index].possible = Some(possible);
+            trial.percent()
+        };
+        let low = percent_with(0.0)?;
+        let high = percent_with(possible)?;
+        if (high - low).abs() < f64::EPSILON {
+            return None;
+        }
+
+        // The mark is linear in the assignment's score once it is graded.
+        let points = (target - low) / (high - low) * possible;
+        Some(NeededScore {
+            points,
+            possible,
+            achievable: (0.0..=possible).contains(&points),
+        })
+    }
+
+    fn percent(&self) -> Option<f64> {
+        let graded = self
+            .assignments
+            .iter()
+            .filter_map(|assignment| Some((assignment, assignment.earned?, assignment.possible?)));
+
+        if self.weights.is_empty() {
+            let (earned, possible) = graded.fold((0.0, 0.0), |(e, p), (_, earned, possible)| {
+                (e + earned, p + possible)
+            });
+            return (possible > 0.0).then(|| earned / possible * 100.0);
+        }
+
+        let mut totals: Vec<(f64, f64)> = vec![(0.0, 0.0); self.weights.len()];
+        for (assignment, earned, possible) in graded {
+            if let Some(i) = self
+                .weights
+                .iter()
+                .position(|(name, _)| name.eq_ignore_ascii_case(&assignment.category))
+            {
+                totals[i].0 += earned;
+                totals[i].1 += possible;
+            }
+        }
+
+        let (weighted, weights) = self
+            .weights
+            .iter()
+            .zip(&totals)
+            .filter(|(_, (_, possible))| *possible > 0.0)
+            .fold(
+                (0.0, 0.0),
+                |(sum, weights), ((_, weight), (earned, possible))| {
+                    (sum + weight * earned / possible * 100.0, weights + weight)
+                },
+            );
+        (weights > 0.0).then(|| weighted / weights)
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.assignments
+            .iter()
+            .position(|assignment| assignment.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    fn find_mut(&mut self, name: &str) -> Option<&mut SimulatedAssignment> {
+        let index = self.position(name)?;
+        self.assignments.get_mut(index)
+    }
+}
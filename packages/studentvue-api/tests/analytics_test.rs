@@ -0,0 +1,111 @@
+use chrono::NaiveDate;
+use studenvue::models::{
+    Absence, AbsencePeriod, AttendanceKind, AttendanceReport, CalendarEvent, CalendarEventKind,
+    PeriodTotals,
+};
+use studenvue::AttendanceAnalytics;
+
+fn date(m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, m, d).unwrap()
+}
+
+fn period(number: &str, course: &str, kind: AttendanceKind) -> AbsencePeriod {
+    AbsencePeriod {
+        number: number.to_string(),
+        name: String::new(),
+        reason: format!("{kind:?}"),
+        kind,
+        course: course.to_string(),
+        staff: String::new(),
+        staff_email: String::new(),
+    }
+}
+
+fn absence(date: &str, periods: Vec<AbsencePeriod>) -> Absence {
+    Absence {
+        date: date.to_string(),
+        reason: String::new(),
+        kind: AttendanceKind::Unknown,
+        note: String::new(),
+        periods,
+    }
+}
+
+fn full_day(date: &str) -> Absence {
+    absence(
+        date,
+        vec![
+            period("1", "Algebra II", AttendanceKind::Excused),
+            period("2", "Biology", AttendanceKind::Excused),
+        ],
+    )
+}
+
+fn report(absences: Vec<Absence>) -> AttendanceReport {
+    AttendanceReport {
+        attendance_type: "Period".to_string(),
+        school_name: "Test High".to_string(),
+        period_count: 2,
+        absences,
+        total_excused: PeriodTotals::default(),
+        total_tardies: PeriodTotals::default(),
+        total_unexcused: PeriodTotals::default(),
+        total_activities: PeriodTotals::default(),
+        total_unexcused_tardies: PeriodTotals::default(),
+    }
+}
+
+#[test]
+fn test_summary_uses_calendar_school_days() {
+    let calendar = vec![CalendarEvent {
+        date: "10/10/2025".to_string(),
+        title: "Teacher Work Day".to_string(),
+        icon: String::new(),
+        day_type: "Holiday".to_string(),
+        start_time: String::new(),
+        link: String::new(),
+        kind: CalendarEventKind::Holiday,
+    }];
+    let report = report(vec![
+        full_day("10/8/2025"),
+        full_day("10/9/2025"),
+        full_day("10/13/2025"),
+        absence(
+            "10/15/2025",
+            vec![period("1", "Algebra II", AttendanceKind::Tardy)],
+        ),
+    ]);
+
+    let summary =
+        AttendanceAnalytics::default().summarize(&report, &calendar, date(10, 1), date(10, 17));
+
+    assert_eq!(summary.school_days, 12);
+    assert_eq!(summary.days_absent, 3);
+    assert!(summary.chronically_absent);
+    assert_eq!(summary.missed_periods_by_course["Algebra II"], 3);
+    assert_eq!(summary.tardies_by_course["Algebra II"], 1);
+    assert_eq!(summary.streaks.len(), 1);
+    assert_eq!(summary.streaks[0].start, date(10, 8));
+    assert_eq!(summary.streaks[0].end, date(10, 13));
+    assert_eq!(summary.streaks[0].days, 3);
+}
+
+#[test]
+fn test_summary_below_chronic_threshold() {
+    let report = report(vec![
+        full_day("10/1/2025"),
+        absence(
+            "10/2/2025",
+            vec![period("2", "Biology", AttendanceKind::Activity)],
+        ),
+    ]);
+
+    let summary = AttendanceAnalytics::new(0.10).summarize(&report, &[], date(10, 1), date(10, 31));
+
+    assert_eq!(summary.school_days, 23);
+    assert_eq!(summary.days_absent, 1);
+    assert!(!summary.chronically_absent);
+    assert!((summary.attendance_rate - 22.0 / 23.0).abs() < 1e-9);
+    // A school activity is not a missed period.
+    assert_eq!(summary.missed_periods_by_course["Biology"], 1);
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use common::MockPortal;
+use std::fs::File;
+use std::path::PathBuf;
+use studenvue::archive::{ArchiveItemKind, ArchiveManifest, MANIFEST_FILE_NAME};
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("studenvue-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn portal() -> MockPortal {
+    let portal = MockPortal::new();
+    portal
+        .respond(
+            "GetStudentDocumentInitialData",
+            r#"<StudentDocuments><StudentDocumentDatas>
+                <StudentDocumentData DocumentGU="DOC-1" DocumentFileName="Transcript.pdf" DocumentDate="6/1/2025" DocumentType="Transcript" DocumentComment="Unofficial" />
+                <StudentDocumentData DocumentGU="DOC-2" DocumentFileName="Transcript.pdf" DocumentDate="6/2/2025" DocumentType="Transcript" DocumentComment="" />
+            </StudentDocumentDatas></StudentDocuments>"#,
+        )
+        .respond(
+            "GetReportCardInitialData",
+            r#"<RCReportingPeriodData><RCReportingPeriods>
+                <RCReportingPeriod DocumentGU="RC-1" ReportingPeriodName="Semester 2" EndDate="6/12/2025" Message="" />
+            </RCReportingPeriods></RCReportingPeriodData>"#,
+        )
+        .respond(
+            "GetContentOfAttachedDoc",
+            &document_data("Transcript.pdf", b"%PDF-1.7 transcript"),
+        );
+    portal
+}
+
+fn document_data(file_name: &str, bytes: &[u8]) -> String {
+    format!(
+        r#"<DocumentData DocumentGU="X" FileName="{file_name}" DocType="PDF"><Base64Code>{}</Base64Code></DocumentData>"#,
+        general_purpose::STANDARD.encode(bytes)
+    )
+}
+
+#[tokio::test]
+async fn test_archive_to_directory_resumes() {
+    let dir = scratch_dir("archive-dir");
+    let portal = portal();
+    let client = portal.start().await;
+
+    let report = client.archive_all_documents(&dir).await.unwrap();
+    assert_eq!(report.downloaded, 2);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].kind, ArchiveItemKind::ReportCard);
+
+    let manifest: ArchiveManifest =
+        serde_json::from_slice(&std::fs::read(dir.join(MANIFEST_FILE_NAME)).unwrap()).unwrap();
+    assert_eq!(manifest.entries.len(), 2);
+    assert!(manifest
+        .entries
+        .iter()
+        .any(|entry| entry.document_gu == "DOC-1"
+            && entry.comment == "Unofficial"
+            && entry.mime_type == "application/pdf"));
+    assert!(dir.join("Transcript.pdf").is_file());
+    assert!(dir.join("Transcript (1).pdf").is_file());
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 3);
+
+    portal.respond(
+        "GetReportCardDocumentData",
+        &document_data("Report Card.pdf", b"%PDF-1.7 report card"),
+    );
+    let report = client.archive_all_documents(&dir).await.unwrap();
+    assert_eq!(report.downloaded, 1);
+    assert_eq!(report.skipped, 2);
+    assert!(report.failed.is_empty());
+    assert_eq!(portal.requests_for("GetContentOfAttachedDoc").len(), 2);
+    assert_eq!(
+        std::fs::read(dir.join("Report Card.pdf")).unwrap(),
+        b"%PDF-1.7 report card"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_archive_to_zip_with_manifest() {
+    let dir = scratch_dir("archive-zip");
+    let path = dir.join("documents.zip");
+    let portal = portal();
+    portal.respond(
+        "GetReportCardDocumentData",
+        &document_data("Report Card.pdf", b"%PDF-1.7 report card"),
+    );
+    let client = portal.start().await;
+
+    let report = client
+        .archive_all_documents_with_concurrency(&path, 2)
+        .await
+        .unwrap();
+    assert_eq!(report.path, path);
+    assert_eq!(report.downloaded, 3);
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    let report = client.archive_all_documents(&path).await.unwrap();
+    assert_eq!(report.downloaded, 0);
+    assert_eq!(report.skipped, 3);
+
+    let mut archive = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+    let mut names: Vec<String> = archive.file_names().map(String::from).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        [
+            "Report Card.pdf",
+            "Transcript (1).pdf",
+            "Transcript.pdf",
+            MANIFEST_FILE_NAME
+        ]
+    );
+
+    let manifest: ArchiveManifest =
+        serde_json::from_reader(archive.by_name(MANIFEST_FILE_NAME).unwrap()).unwrap();
+    let report_card = manifest
+        .entries
+        .iter()
+        .find(|entry| entry.kind == ArchiveItemKind::ReportCard)
+        .unwrap();
+    assert_eq!(report_card.date, "6/12/2025");
+    assert_eq!(report_card.comment, "Semester 2");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_failed_zip_archive_removes_partial_file() {
+    let dir = scratch_dir("archive-corrupt");
+    let path = dir.join("documents.zip");
+    std::fs::write(&path, b"not a zip file").unwrap();
+    let portal = portal();
+    let client = portal.start().await;
+
+    assert!(client.archive_all_documents(&path).await.is_err());
+    assert!(!dir.join("documents.zip.partial").exists());
+    assert_eq!(std::fs::read(&path).unwrap(), b"not a zip file");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::{assignment, course, mark};
+use studenvue::assignments::{assignment_overview, missing_status, AssignmentStatus};
+
+#[test]
+fn test_missing_status() {
+    let mut nti = assignment("1", "Essay", "10/1/2025", "Not Turned In", "");
+    assert_eq!(missing_status(&nti), Some(AssignmentStatus::NotTurnedIn));
+
+    nti.score = String::new();
+    nti.notes = "MISSING - see me".to_string();
+    assert_eq!(missing_status(&nti), Some(AssignmentStatus::Missing));
+
+    let zero = assignment(
+        "2",
+        "Quiz",
+        "10/1/2025",
+        "0 out of 10.0000",
+        "0.00 / 10.0000",
+    );
+    assert_eq!(missing_status(&zero), Some(AssignmentStatus::Zero));
+
+    let graded = assignment("3", "Quiz", "10/1/2025", "", "9.00 / 10.0000");
+    assert_eq!(missing_status(&graded), None);
+}
+
+#[test]
+fn test_assignment_overview() {
+    let essay = assignment("E1", "Essay", "10/6/2025", "Missing", "");
+    let courses = vec![
+        course(
+            "1",
+            "English 10",
+            "Park, Jo",
+            vec![
+                mark(
+                    "Quarter 1",
+                    "",
+                    vec![
+                        essay.clone(),
+                        assignment("E2", "Reading Log", "10/14/2025", "", "10 Points Possible"),
+                    ],
+                ),
+                // The semester mark repeats the quarter's assignments.
+                mark("Semester 1", "", vec![essay]),
+            ],
+        ),
+        course(
+            "1",
+            "Chemistry",
+            "Ortiz, Sam",
+            vec![mark(
+                "Quarter 1",
+                "",
+                vec![
+                    assignment("C1", "Lab 3", "10/2/2025", "", "0.00 / 20.0000"),
+                    assignment("C2", "Lab 4", "10/9/2025", "", "20 Points Possible"),
+                    assignment("C3", "Unit Test", "10/30/2025", "", "100 Points Possible"),
+                    assignment("C4", "Lab 2", "9/25/2025", "", "20 Points Possible"),
+                ],
+            )],
+        ),
+    ];
+    let today = NaiveDate::from_ymd_opt(2025, 10, 8).unwrap();
+
+    let overview = assignment_overview(&courses, today, 7);
+
+    let missing: Vec<&str> = overview
+        .missing
+        .iter()
+        .map(|item| item.assignment.measure.as_str())
+        .collect();
+    assert_eq!(missing, ["Lab 3", "Essay"]);
+    assert_eq!(overview.missing[0].status, AssignmentStatus::Zero);
+    assert_eq!(overview.missing[1].teacher(), "Park, Jo");
+
+    let upcoming: Vec<&str> = overview
+        .upcoming
+        .iter()
+        .map(|item| item.assignment.measure.as_str())
+        .collect();
+    assert_eq!(upcoming, ["Lab 4", "Reading Log"]);
+    assert_eq!(overview.upcoming[0].course.title, "Chemistry");
+    assert_eq!(
+        overview.upcoming[1].due,
+        NaiveDate::from_ymd_opt(2025, 10, 14)
+    );
+
+    // A window too large to add to a date reaches as far as dates go.
+    let everything = assignment_overview(&courses, today, i64::MAX);
+    assert_eq!(everything.upcoming.len(), 3);
+    assert!(assignment_overview(&courses, today, i64::MIN)
+        .upcoming
+        .is_empty());
+}
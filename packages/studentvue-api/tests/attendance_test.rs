@@ -0,0 +1,96 @@
+mod common;
+
+use common::MockPortal;
+use studenvue::models::{Absence, AttendanceKind};
+use studenvue::AttendanceCodes;
+
+const ATTENDANCE: &str = r#"<Attendance Type="Period" StartPeriod="1" EndPeriod="7" PeriodCount="7" SchoolName="Lincoln High">
+<Absences>
+<Absence AbsenceDate="9/10/2025" Reason="Excused" Note="Doctor">
+<Periods>
+<Period Number="1" Name="Algebra II" Reason="Excused" Course="Algebra II" Staff="Lee, Dana" StaffEMail="dlee@lincoln.edu" />
+<Period Number="2" Name="Chemistry" Reason="Excused" Course="Chemistry" Staff="Ortiz, Chris" StaffEMail="" />
+</Periods>
+</Absence>
+</Absences>
+<TotalExcused><PeriodTotal Number="1" Total="3" /><PeriodTotal Number="2" Total="2" /></TotalExcused>
+<TotalTardies><PeriodTotal Number="1" Total="1" /></TotalTardies>
+<TotalUnexcused><PeriodTotal Number="3" Total="4" /></TotalUnexcused>
+<TotalActivities><PeriodTotal Number="5" Total="2" /></TotalActivities>
+<TotalUnexcusedTardies><PeriodTotal Number="1" Total="0" /></TotalUnexcusedTardies>
+</Attendance>"#;
+
+#[tokio::test]
+async fn test_attendance_report_totals() {
+    let portal = MockPortal::new();
+    portal.respond("Attendance", ATTENDANCE);
+    let client = portal.start().await;
+
+    let report = client.get_attendance_report().await.unwrap();
+
+    assert_eq!(report.attendance_type, "Period");
+    assert_eq!(report.school_name, "Lincoln High");
+    assert_eq!(report.period_count, 7);
+    assert_eq!(report.absences.len(), 1);
+    assert_eq!(report.absences[0].periods.len(), 2);
+    assert_eq!(report.absences[0].periods[1].course, "Chemistry");
+
+    assert_eq!(report.total_excused.periods.len(), 2);
+    assert_eq!(report.total_excused.sum(), 5);
+    assert_eq!(report.total_excused.for_period(2), 2);
+    assert_eq!(report.total_tardies.for_period(1), 1);
+    assert_eq!(report.total_unexcused.for_period(3), 4);
+    assert_eq!(report.total_unexcused.for_period(1), 0);
+    assert_eq!(report.total_activities.sum(), 2);
+    assert_eq!(report.total_unexcused_tardies.sum(), 0);
+    assert_eq!(
+        report.totals_for(AttendanceKind::Unexcused).unwrap().sum(),
+        4
+    );
+}
+
+#[test]
+fn test_default_codes_classify_common_reasons() {
+    let codes = AttendanceCodes::default();
+
+    assert_eq!(codes.classify("Excused"), AttendanceKind::Excused);
+    assert_eq!(codes.classify(" unexcused "), AttendanceKind::Unexcused);
+    assert_eq!(codes.classify("TARDY"), AttendanceKind::Tardy);
+    assert_eq!(codes.classify("Activity"), AttendanceKind::Activity);
+    assert_eq!(codes.classify("Vacation"), AttendanceKind::Unknown);
+}
+
+#[test]
+fn test_custom_codes_override_defaults() {
+    let codes = AttendanceCodes::default()
+        .with("EXC-M", AttendanceKind::Excused)
+        .with("Tardy", AttendanceKind::UnexcusedTardy);
+
+    assert_eq!(codes.classify("exc-m"), AttendanceKind::Excused);
+    assert_eq!(codes.classify("Tardy"), AttendanceKind::UnexcusedTardy);
+    assert_eq!(
+        AttendanceCodes::empty().classify("Excused"),
+        AttendanceKind::Unknown
+    );
+}
+
+#[test]
+fn test_absences_saved_without_kind_still_load() {
+    let json = r#"{
+        "date": "9/10/2025",
+        "reason": "Excused",
+        "note": "",
+        "periods": [{
+            "number": "1",
+            "name": "Algebra II",
+            "reason": "Excused",
+            "course": "Algebra II",
+            "staff": "Lee, Dana",
+            "staff_email": ""
+        }]
+    }"#;
+
+    let absence: Absence = serde_json::from_str(json).unwrap();
+    assert_eq!(absence.kind, AttendanceKind::Unknown);
+    assert_eq!(absence.periods[0].kind, AttendanceKind::Unknown);
+}
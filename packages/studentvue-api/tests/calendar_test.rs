@@ -0,0 +1,135 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::MockPortal;
+use studenvue::calendar::link_assignments;
+use studenvue::models::{CalendarEvent, CalendarEventKind};
+
+const CALENDAR: &str = r#"<CalendarListing SchoolBegDate="8/25/2025" SchoolEndDate="6/12/2026" MonthBegDate="10/1/2025"><EventLists>
+<EventList Date="9/1/2025" Title="Labor Day" DayType="Holiday" />
+<EventList Date="10/13/2025" Title="No School - Staff Development" DayType="Holiday" />
+<EventList Date="9/19/2025" Title="Homecoming" DayType="Regular" />
+<EventList Date="10/3/2025" Title="Algebra II: Quiz 2" DayType="Assignment" />
+</EventLists></CalendarListing>"#;
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[tokio::test]
+async fn test_get_calendar_listing_header() {
+    let portal = MockPortal::new();
+    portal.respond("StudentCalendar", CALENDAR);
+    let client = portal.start().await;
+
+    let listing = client.get_calendar_listing("10/1/2025").await.unwrap();
+    assert_eq!(listing.school_begin_date, "8/25/2025");
+    assert_eq!(listing.school_end_date, "6/12/2026");
+    assert_eq!(listing.month_begin_date, "10/1/2025");
+    assert_eq!(listing.events.len(), 4);
+}
+
+#[tokio::test]
+async fn test_get_calendar_range_requests_each_month_and_dedups() {
+    let portal = MockPortal::new();
+    portal.respond("StudentCalendar", CALENDAR);
+    let client = portal.start().await;
+
+    let range = client
+        .get_calendar_range(date(2025, 9, 15), date(2025, 11, 10))
+        .await
+        .unwrap();
+
+    let requests = portal.requests_for("StudentCalendar");
+    assert_eq!(requests.len(), 3);
+    for month in ["9/1/2025", "10/1/2025", "11/1/2025"] {
+        let param = format!("<RequestDate>{month}</RequestDate>");
+        assert!(requests.iter().any(|r| r.params.contains(&param)));
+    }
+
+    assert_eq!(range.school_begin_date, Some(date(2025, 8, 25)));
+    assert_eq!(range.school_end_date, Some(date(2026, 6, 12)));
+    let titles: Vec<&str> = range.events.iter().map(|e| e.title.as_str()).collect();
+    assert_eq!(
+        titles,
+        [
+            "Homecoming",
+            "Algebra II: Quiz 2",
+            "No School - Staff Development"
+        ]
+    );
+}
+
+const GRADEBOOK: &str = r#"<Gradebook><Courses>
+<Course Period="2" Title="Algebra II" Room="204" Staff="Lee, Dana" StaffEMail="dlee@example.edu"><Marks>
+<Mark MarkName="Quarter 1" CalculatedScoreString="B+" CalculatedScoreRaw="88.4"><Assignments>
+<Assignment GradebookID="GB-42" Measure="Quiz 2" Type="Quizzes" Date="10/3/2025" DueDate="10/3/2025" Score="Not Graded" ScoreType="Raw Score" Points="10 Points Possible" Notes="" />
+</Assignments></Mark>
+</Marks></Course>
+</Courses></Gradebook>"#;
+
+const ASSIGNMENT_CALENDAR: &str = r#"<CalendarListing SchoolBegDate="8/25/2025" SchoolEndDate="6/12/2026" MonthBegDate="10/1/2025"><EventLists>
+<EventList Date="10/3/2025" Title="Algebra II: Quiz 2" DayType="Assignment" StartTime="8:00 AM" AGU="GB-42" DGU="DGU-7" ViewType="1" />
+<EventList Date="10/13/2025" Title="Staff Development" DayType="Holiday" />
+<EventList Date="10/17/2025" Title="Picture Day" DayType="Regular" />
+</EventLists></CalendarListing>"#;
+
+#[tokio::test]
+async fn test_calendar_event_kinds_link_to_gradebook() {
+    let portal = MockPortal::new();
+    portal
+        .respond("StudentCalendar", ASSIGNMENT_CALENDAR)
+        .respond("Gradebook", GRADEBOOK);
+    let client = portal.start().await;
+
+    let events = client.get_calendar("10/1/2025").await.unwrap();
+    assert_eq!(
+        events[0].kind,
+        CalendarEventKind::Assignment {
+            assignment_gu: "GB-42".to_string(),
+            gradebook_gu: "DGU-7".to_string(),
+            view_type: "1".to_string(),
+        }
+    );
+    assert_eq!(events[0].start_time, "8:00 AM");
+    assert!(events[1].is_school_closed());
+    assert_eq!(events[2].kind, CalendarEventKind::SchoolEvent);
+
+    let courses = client.get_gradebook(None).await.unwrap();
+    assert_eq!(courses[0].marks[0].assignments.len(), 1);
+
+    let links = link_assignments(&events, &courses);
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].course.title, "Algebra II");
+    assert_eq!(links[0].assignment.measure, "Quiz 2");
+}
+
+#[tokio::test]
+async fn test_get_calendar_range_limits_requests_in_flight() {
+    let portal = MockPortal::new().with_chunk_size(64);
+    portal.respond("StudentCalendar", CALENDAR);
+    let client = portal.start().await;
+
+    client
+        .get_calendar_range(date(2025, 8, 1), date(2026, 6, 30))
+        .await
+        .unwrap();
+
+    assert_eq!(portal.requests_for("StudentCalendar").len(), 11);
+    assert!(portal.max_in_flight() <= 4);
+}
+
+#[test]
+fn test_calendar_events_saved_before_kinds_still_load() {
+    let json = r#"{
+        "date": "9/1/2025",
+        "title": "Labor Day",
+        "icon": "",
+        "day_type": "Holiday"
+    }"#;
+
+    let event: CalendarEvent = serde_json::from_str(json).unwrap();
+    assert_eq!(event.title, "Labor Day");
+    assert!(event.start_time.is_empty());
+    assert_eq!(event.kind, CalendarEventKind::Other(String::new()));
+}
@@ -0,0 +1,296 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use studenvue::models::{Assignment, Course, Mark};
+use studenvue::StudentVueClient;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A request received by [`MockPortal`], with the SOAP envelope already unpacked.
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub path: String,
+    pub soap_action: String,
+    pub method_name: String,
+    pub multi_web: bool,
+    pub params: String,
+}
+
+/// A local stand-in for a district's StudentVue portal.
+///
+/// Responses are registered per SOAP method name as the inner XML the portal
+/// would return; the mock wraps them in a SOAP envelope the same way the real
+/// service does. Unregistered methods answer with an `RT_ERROR`.
+#[derive(Clone, Default)]
+pub struct MockPortal {
+    responses: Arc<Mutex<HashMap<String, String>>>,
+    matched_responses: Arc<Mutex<Vec<(String, String, String)>>>,
+    failures: Arc<Mutex<HashMap<String, usize>>>,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+    chunk_size: Option<usize>,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+impl MockPortal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends response bodies in pieces of `size` bytes with a short pause
+    /// between them, so clients see the body arrive over several reads.
+    pub fn with_chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = Some(size);
+        self
+    }
+
+    pub fn respond(&self, method_name: &str, xml: &str) -> &Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .insert(method_name.to_string(), xml.to_string());
+        self
+    }
+
+    /// Registers a response used only when the request's parameters contain
+    /// `params_fragment`, taking precedence over [`MockPortal::respond`].
+    pub fn respond_matching(&self, method_name: &str, params_fragment: &str, xml: &str) -> &Self {
+        self.matched_responses.lock().unwrap().push((
+            method_name.to_string(),
+            params_fragment.to_string(),
+            xml.to_string(),
+        ));
+        self
+    }
+
+    /// Answers the next `times` requests for `method_name` with an HTTP 500,
+    /// as a portal that is down would, before any registered response.
+    pub fn fail_next(&self, method_name: &str, times: usize) -> &Self {
+        self.failures
+            .lock()
+            .unwrap()
+            .insert(method_name.to_string(), times);
+        self
+    }
+
+    /// The most requests that were being answered at the same time.
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight.load(Ordering::SeqCst)
+    }
+
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn requests_for(&self, method_name: &str) -> Vec<MockRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|request| request.method_name == method_name)
+            .collect()
+    }
+
+    /// Starts serving on an ephemeral local port and returns a client pointed at it.
+    pub async fn start(&self) -> StudentVueClient {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let portal = self.clone();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let portal = portal.clone();
+                tokio::spawn(async move { portal.handle(stream).await });
+            }
+        });
+
+        StudentVueClient::new(base_url, "student".to_string(), "secret".to_string())
+    }
+
+    async fn handle(&self, mut stream: TcpStream) {
+        let mut data = Vec::new();
+        let mut chunk = [0u8; 8192];
+
+        let header_end = loop {
+            let read = stream.read(&mut chunk).await.unwrap_or(0);
+            if read == 0 {
+                return;
+            }
+            data.extend_from_slice(&chunk[..read]);
+            if let Some(pos) = find(&data, b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&data[..header_end]).to_string();
+        let content_length = header_value(&head, "content-length")
+            .and_then(|value| value.parse::<usize>().ok())
+            .unwrap_or(0);
+        while data.len() < header_end + content_length {
+            let read = stream.read(&mut chunk).await.unwrap_or(0);
+            if read == 0 {
+                break;
+            }
+            data.extend_from_slice(&chunk[..read]);
+        }
+
+        let body = String::from_utf8_lossy(&data[header_end..]).to_string();
+        let request = MockRequest {
+            path: head.split_whitespace().nth(1).unwrap_or("").to_string(),
+            soap_action: header_value(&head, "soapaction").unwrap_or_default(),
+            method_name: element_text(&body, "methodName"),
+            multi_web: body.contains("ProcessWebServiceRequestMultiWeb"),
+            params: unescape(&element_text(&body, "paramStr")),
+        };
+
+        let result_tag = if request.multi_web {
+            "ProcessWebServiceRequestMultiWebResult"
+        } else {
+            "ProcessWebServiceRequestResult"
+        };
+        let failed = match self.failures.lock().unwrap().get_mut(&request.method_name) {
+            Some(remaining) if *remaining > 0 => {
+                *remaining -= 1;
+                true
+            }
+            _ => false,
+        };
+        if failed {
+            self.requests.lock().unwrap().push(request);
+            let _ = stream
+                .write_all(
+                    b"HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                )
+                .await;
+            let _ = stream.shutdown().await;
+            return;
+        }
+        let matched = self
+            .matched_responses
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(method_name, fragment, _)| {
+                *method_name == request.method_name && request.params.contains(fragment.as_str())
+            })
+            .map(|(_, _, xml)| xml.clone());
+        let inner = matched
+            .or_else(|| {
+                self.responses
+                    .lock()
+                    .unwrap()
+                    .get(&request.method_name)
+                    .cloned()
+            })
+            .unwrap_or_else(|| {
+                format!(
+                    r#"<RT_ERROR ERROR_MESSAGE="Unknown method {}" />"#,
+                    request.method_name
+                )
+            });
+        self.requests.lock().unwrap().push(request);
+        let active = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(active, Ordering::SeqCst);
+
+        let envelope = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?><soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/"><soap:Body><{result_tag}>{}</{result_tag}></soap:Body></soap:Envelope>"#,
+            escape(&inner)
+        );
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/xml; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            envelope.len()
+        );
+        let _ = stream.write_all(head.as_bytes()).await;
+        // The request stops counting as in flight before its last bytes go out,
+        // so the client cannot have moved on to another request while it is
+        // still counted.
+        let pieces: Vec<&[u8]> = match self.chunk_size {
+            Some(size) => envelope.as_bytes().chunks(size).collect(),
+            None => vec![envelope.as_bytes()],
+        };
+        for (i, piece) in pieces.iter().enumerate() {
+            if i + 1 == pieces.len() {
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            }
+            let _ = stream.write_all(piece).await;
+            if self.chunk_size.is_some() {
+                let _ = stream.flush().await;
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            }
+        }
+        let _ = stream.shutdown().await;
+    }
+}
+
+/// A homework assignment due on `due`, with `score` and `points` written the
+/// way the gradebook reports them.
+pub fn assignment(id: &str, measure: &str, due: &str, score: &str, points: &str) -> Assignment {
+    Assignment {
+        gradebook_id: id.to_string(),
+        measure: measure.to_string(),
+        assignment_type: "Homework".to_string(),
+        date: due.to_string(),
+        due_date: due.to_string(),
+        score: score.to_string(),
+        score_type: "Raw Score".to_string(),
+        points: points.to_string(),
+        ..Default::default()
+    }
+}
+
+pub fn mark(name: &str, score: &str, assignments: Vec<Assignment>) -> Mark {
+    Mark {
+        mark_name: name.to_string(),
+        score: score.to_string(),
+        assignments,
+        ..Default::default()
+    }
+}
+
+pub fn course(period: &str, title: &str, staff: &str, marks: Vec<Mark>) -> Course {
+    Course {
+        period: period.to_string(),
+        title: title.to_string(),
+        staff: staff.to_string(),
+        marks,
+        ..Default::default()
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn header_value(head: &str, name: &str) -> Option<String> {
+    head.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim().to_string())
+    })
+}
+
+fn element_text(xml: &str, tag: &str) -> String {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    xml.split_once(&open)
+        .and_then(|(_, rest)| rest.split_once(&close))
+        .map(|(text, _)| text.to_string())
+        .unwrap_or_default()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
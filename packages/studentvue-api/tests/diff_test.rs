@@ -0,0 +1,346 @@
+mod common;
+
+use chrono::Local;
+use common::{assignment, course, mark};
+use studenvue::diff::{diff, Change};
+use studenvue::models::*;
+use studenvue::snapshot::{Snapshot, SnapshotEndpoint, SnapshotError};
+
+fn gradebook(period: &str, marks: Vec<Mark>) -> Gradebook {
+    Gradebook {
+        reporting_periods: Vec::new(),
+        reporting_period: Some(ReportPeriod {
+            index: None,
+            name: period.to_string(),
+            start_date: None,
+            end_date: None,
+        }),
+        courses: vec![Course {
+            room: "101".to_string(),
+            ..course("1", "Algebra II", "Dana Lee", marks)
+        }],
+    }
+}
+
+fn class(period: &str, title: &str, room: &str) -> ClassSchedule {
+    ClassSchedule {
+        period: period.to_string(),
+        course_title: title.to_string(),
+        room_name: room.to_string(),
+        teacher: "Dana Lee".to_string(),
+        teacher_email: String::new(),
+    }
+}
+
+fn absence(date: &str, periods: &[&str]) -> Absence {
+    Absence {
+        date: date.to_string(),
+        reason: "Illness".to_string(),
+        kind: AttendanceKind::Excused,
+        note: String::new(),
+        periods: periods
+            .iter()
+            .map(|number| AbsencePeriod {
+                number: number.to_string(),
+                name: format!("Period {number}"),
+                reason: "Illness".to_string(),
+                kind: AttendanceKind::Excused,
+                course: String::new(),
+                staff: String::new(),
+                staff_email: String::new(),
+            })
+            .collect(),
+    }
+}
+
+fn attendance(absences: Vec<Absence>) -> AttendanceReport {
+    AttendanceReport {
+        attendance_type: "Period".to_string(),
+        school_name: "Lincoln High".to_string(),
+        period_count: 7,
+        absences,
+        total_excused: PeriodTotals::default(),
+        total_tardies: PeriodTotals::default(),
+        total_unexcused: PeriodTotals::default(),
+        total_activities: PeriodTotals::default(),
+        total_unexcused_tardies: PeriodTotals::default(),
+    }
+}
+
+fn message(id: &str) -> Message {
+    Message {
+        id: id.to_string(),
+        subject: "Field trip".to_string(),
+        subject_no_html: "Field trip".to_string(),
+        content: String::new(),
+        from: "Lee, Dana".to_string(),
+        sender: MessageSender {
+            name: "Lee, Dana".to_string(),
+            person_gu: String::new(),
+            email: String::new(),
+        },
+        date: "10/14/2025".to_string(),
+        read: false,
+        deletable: true,
+        message_type: "StudentActivity".to_string(),
+        module_name: String::new(),
+        attachments: Vec::new(),
+    }
+}
+
+fn document(gu: &str) -> Document {
+    Document {
+        document_gu: gu.to_string(),
+        file_name: "Transcript.pdf".to_string(),
+        date: "10/15/2025".to_string(),
+        document_type: "Transcript".to_string(),
+        comment: String::new(),
+    }
+}
+
+fn report_card(gu: &str, period: &str) -> ReportCard {
+    ReportCard {
+        document_gu: gu.to_string(),
+        reporting_period: period.to_string(),
+        end_date: String::new(),
+        message: String::new(),
+    }
+}
+
+fn snapshot() -> Snapshot {
+    Snapshot {
+        taken_at: Local::now(),
+        student_info: None,
+        school_info: None,
+        schedule: Some(vec![
+            class("1", "Algebra II", "101"),
+            class("2", "Chemistry", "210"),
+            class("3", "Spanish I", "305"),
+        ]),
+        gradebooks: vec![gradebook(
+            "Quarter 1",
+            vec![mark(
+                "Q1",
+                "B",
+                vec![
+                    assignment("A1", "Quiz 1", "10/3/2025", "8 out of 10", "8 / 10"),
+                    assignment(
+                        "A2",
+                        "Quiz 2",
+                        "10/3/2025",
+                        "Not Graded",
+                        "10 Points Possible",
+                    ),
+                ],
+            )],
+        )],
+        attendance: Some(attendance(vec![absence("9/10/2025", &["1"])])),
+        messages: Some(vec![message("MSG-1")]),
+        calendar: None,
+        documents: Some(vec![document("DOC-1")]),
+        report_cards: Some(Vec::new()),
+        health: None,
+        errors: Vec::new(),
+    }
+}
+
+#[test]
+fn test_identical_snapshots_have_no_changes() {
+    assert!(diff(&snapshot(), &snapshot()).is_empty());
+}
+
+#[test]
+fn test_grade_changes() {
+    let before = snapshot();
+    let mut after = snapshot();
+    after.gradebooks[0].courses[0].marks = vec![
+        mark(
+            "Q1",
+            "B+",
+            vec![
+                assignment("A1", "Quiz 1", "10/3/2025", "9 out of 10", "9 / 10"),
+                assignment("A2", "Quiz 2", "10/3/2025", "10 out of 10", "10 / 10"),
+                assignment("A3", "Homework 4", "10/3/2025", "5 out of 5", "5 / 5"),
+            ],
+        ),
+        // The semester mark repeats the quarter's assignments.
+        mark(
+            "S1",
+            "",
+            vec![assignment(
+                "A2",
+                "Quiz 2",
+                "10/3/2025",
+                "10 out of 10",
+                "10 / 10",
+            )],
+        ),
+    ];
+
+    let changes = diff(&before, &after);
+    assert_eq!(changes.len(), 4);
+
+    let Change::MarkChanged {
+        reporting_period,
+        course,
+        before: Some(old),
+        after: new,
+    } = &changes[0]
+    else {
+        panic!("expected a mark change, got {:?}", changes[0]);
+    };
+    assert_eq!(reporting_period, "Quarter 1");
+    assert_eq!(course, "Algebra II");
+    assert_eq!((old.score.as_str(), new.score.as_str()), ("B", "B+"));
+
+    let Change::ScoreChanged { before, after, .. } = &changes[1] else {
+        panic!("expected a score change, got {:?}", changes[1]);
+    };
+    assert_eq!(
+        (before.points.as_str(), after.points.as_str()),
+        ("8 / 10", "9 / 10")
+    );
+
+    let Change::AssignmentScored { before, after, .. } = &changes[2] else {
+        panic!("expected a newly scored assignment, got {:?}", changes[2]);
+    };
+    assert_eq!(before.as_ref().unwrap().score, "Not Graded");
+    assert_eq!(after.measure, "Quiz 2");
+
+    let Change::AssignmentScored { before, after, .. } = &changes[3] else {
+        panic!("expected a newly scored assignment, got {:?}", changes[3]);
+    };
+    assert!(before.is_none());
+    assert_eq!(after.measure, "Homework 4");
+}
+
+#[test]
+fn test_retracted_score() {
+    let before = snapshot();
+    let mut after = snapshot();
+    after.gradebooks[0].courses[0].marks[0].assignments[0] = assignment(
+        "A1",
+        "Quiz 1",
+        "10/3/2025",
+        "Not Graded",
+        "10 Points Possible",
+    );
+
+    let changes = diff(&before, &after);
+    assert_eq!(changes.len(), 1);
+
+    let Change::ScoreRemoved {
+        course,
+        before,
+        after,
+        ..
+    } = &changes[0]
+    else {
+        panic!("expected a removed score, got {:?}", changes[0]);
+    };
+    assert_eq!(course, "Algebra II");
+    assert_eq!(before.score, "8 out of 10");
+    assert_eq!(after.score, "Not Graded");
+}
+
+#[test]
+fn test_new_items() {
+    let before = snapshot();
+    let mut after = snapshot();
+    after.attendance = Some(attendance(vec![
+        absence("9/10/2025", &["1", "2"]),
+        absence("10/2/2025", &["3"]),
+    ]));
+    after.messages.as_mut().unwrap().push(message("MSG-2"));
+    after.documents.as_mut().unwrap().push(document("DOC-2"));
+    after.report_cards = Some(vec![report_card("RC-1", "Quarter 1")]);
+
+    let changes = diff(&before, &after);
+    assert_eq!(changes.len(), 5);
+
+    assert!(matches!(
+        &changes[0],
+        Change::NewAbsence { before: Some(old), after: new }
+            if old.periods.len() == 1 && new.periods.len() == 2
+    ));
+    assert!(matches!(
+        &changes[1],
+        Change::NewAbsence { before: None, after } if after.date == "10/2/2025"
+    ));
+    assert!(matches!(
+        &changes[2],
+        Change::NewMessage { before: None, after } if after.id == "MSG-2"
+    ));
+    assert!(matches!(
+        &changes[3],
+        Change::NewDocument { after, .. } if after.document_gu == "DOC-2"
+    ));
+    assert!(matches!(
+        &changes[4],
+        Change::NewReportCard { after, .. } if after.reporting_period == "Quarter 1"
+    ));
+}
+
+#[test]
+fn test_schedule_changes() {
+    let before = snapshot();
+    let mut after = snapshot();
+    after.schedule = Some(vec![
+        class("01", "Algebra II", "B-101"),
+        class("2", "Physics", "210"),
+        class("4", "Art", "120"),
+    ]);
+
+    let changes = diff(&before, &after);
+    let schedule: Vec<(Option<&str>, Option<&str>)> = changes
+        .iter()
+        .map(|change| match change {
+            Change::ScheduleChanged { before, after } => (
+                before.as_ref().map(|class| class.course_title.as_str()),
+                after.as_ref().map(|class| class.course_title.as_str()),
+            ),
+            other => panic!("unexpected change {other:?}"),
+        })
+        .collect();
+
+    assert_eq!(
+        schedule,
+        [
+            (Some("Algebra II"), Some("Algebra II")),
+            (Some("Chemistry"), Some("Physics")),
+            (None, Some("Art")),
+            (Some("Spanish I"), None),
+        ]
+    );
+}
+
+#[test]
+fn test_failed_endpoints_are_skipped() {
+    let mut before = snapshot();
+    before.messages = None;
+    before.gradebooks.clear();
+    before.errors = vec![
+        SnapshotError {
+            endpoint: SnapshotEndpoint::Messages,
+            reporting_period: None,
+            error: "timed out".to_string(),
+        },
+        SnapshotError {
+            endpoint: SnapshotEndpoint::Gradebook,
+            reporting_period: Some(0),
+            error: "timed out".to_string(),
+        },
+    ];
+    let mut after = snapshot();
+    after.documents = None;
+
+    assert!(diff(&before, &after).is_empty());
+
+    // Without a recorded failure, a new reporting period's grades are news.
+    before.errors.clear();
+    let changes = diff(&before, &after);
+    assert!(changes
+        .iter()
+        .any(|change| matches!(change, Change::MarkChanged { before: None, .. })));
+    assert_eq!(after.changes_since(&before).len(), changes.len());
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use common::MockPortal;
+use std::path::PathBuf;
+use studenvue::document::sanitize_file_name;
+use studenvue::models::DocumentData;
+use studenvue::Error;
+
+fn document(file_name: &str, bytes: &[u8]) -> DocumentData {
+    let encoded = general_purpose::STANDARD.encode(bytes);
+    DocumentData {
+        document_gu: "DOC-1".to_string(),
+        file_name: file_name.to_string(),
+        doc_type: "PDF".to_string(),
+        base64_content: format!("{}\n{}", &encoded[..8], &encoded[8..]),
+    }
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("studenvue-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_decode_detects_mime_and_extension() {
+    let decoded = document("Report Card Q1", b"%PDF-1.7\n...")
+        .decode()
+        .unwrap();
+
+    assert_eq!(decoded.mime_type, "application/pdf");
+    assert_eq!(decoded.file_name, "Report Card Q1.pdf");
+    assert_eq!(decoded.bytes, b"%PDF-1.7\n...");
+
+    let png = document("scan.png", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")
+        .decode()
+        .unwrap();
+    assert_eq!(png.mime_type, "image/png");
+}
+
+#[test]
+fn test_sanitize_file_name() {
+    assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+    assert_eq!(sanitize_file_name("..\\..\\boot.ini"), "boot.ini");
+    assert_eq!(sanitize_file_name("a<b>:c?.pdf"), "a_b__c_.pdf");
+    assert_eq!(sanitize_file_name(".."), "document");
+    assert_eq!(sanitize_file_name("CON.txt"), "_CON.txt");
+    assert_eq!(sanitize_file_name(&"x".repeat(300)).len(), 200);
+}
+
+#[test]
+fn test_save_to_avoids_traversal_and_collisions() {
+    let dir = scratch_dir("save");
+    let data = document("../secret/report.pdf", b"%PDF-1.4");
+
+    let first = data.save_to(&dir).unwrap();
+    let second = data.save_to(&dir).unwrap();
+
+    assert_eq!(first, dir.join("report.pdf"));
+    assert_eq!(second, dir.join("report (1).pdf"));
+    assert_eq!(std::fs::read(&second).unwrap(), b"%PDF-1.4");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_download_document_streams_across_chunks() {
+    let bytes: Vec<u8> = (0..50_000u32).map(|i| (i * 7 % 251) as u8).collect();
+    let encoded = general_purpose::STANDARD.encode(&bytes);
+    let wrapped: Vec<&str> = encoded
+        .as_bytes()
+        .chunks(76)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect();
+
+    let portal = MockPortal::new().with_chunk_size(997);
+    portal.respond(
+        "GetContentOfAttachedDoc",
+        &format!(
+            r#"<AttachmentXML DocumentGU="DOC-9"><AttachmentDatas><AttachmentData DocumentName="Field Trip Form.pdf"><Base64Code>{}</Base64Code></AttachmentData></AttachmentDatas></AttachmentXML>"#,
+            wrapped.join("&#xD;\n")
+        ),
+    );
+    let client = portal.start().await;
+
+    let mut output = Vec::new();
+    let streamed = client
+        .download_document("DOC-9", &mut output)
+        .await
+        .unwrap();
+
+    assert_eq!(output, bytes);
+    assert_eq!(streamed.bytes_written, bytes.len() as u64);
+    assert_eq!(streamed.file_name, "Field Trip Form.pdf");
+    assert_eq!(streamed.doc_type, "PDF");
+    assert_eq!(streamed.document_gu, "DOC-9");
+}
+
+#[tokio::test]
+async fn test_download_report_card_surfaces_portal_errors() {
+    let client = MockPortal::new().start().await;
+
+    let error = client
+        .download_report_card("RC-1", &mut Vec::new())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, Error::InvalidResponse(message) if message.contains("Unknown method")));
+}
+
+#[tokio::test]
+async fn test_attachment_doc_type_from_content() {
+    let portal = MockPortal::new();
+    portal
+        .respond_matching(
+            "SynergyMailGetAttachment",
+            "ATT-PDF",
+            r#"<AttachmentXML DocumentName="scan"><Base64Code>JVBERi0xLjQ=</Base64Code></AttachmentXML>"#,
+        )
+        .respond(
+            "SynergyMailGetAttachment",
+            r#"<AttachmentXML DocumentName="notes"><Base64Code>JVBé</Base64Code></AttachmentXML>"#,
+        );
+    let client = portal.start().await;
+
+    let pdf = client.get_message_attachment("ATT-PDF").await.unwrap();
+    assert_eq!(pdf.doc_type, "PDF");
+
+    // Stray non-ASCII content must not split a character when truncating.
+    let garbled = client.get_message_attachment("ATT-2").await.unwrap();
+    assert_eq!(garbled.doc_type, "unknown");
+}
@@ -0,0 +1,143 @@
+use studenvue::gpa::{CourseLevel, GpaCourse, GradeScale};
+use studenvue::models::{Course, CourseHistory, HistoryCourse, HistoryTerm, Mark};
+use studenvue::GpaCalculator;
+
+fn history_course(title: &str, mark: &str, credits: f64, course_type: &str) -> HistoryCourse {
+    HistoryCourse {
+        course_id: String::new(),
+        course_title: title.to_string(),
+        mark: mark.to_string(),
+        credits_attempted: Some(credits),
+        credits_earned: Some(credits),
+        course_type: course_type.to_string(),
+        subject_area: String::new(),
+    }
+}
+
+fn term(name: &str, courses: Vec<HistoryCourse>) -> HistoryTerm {
+    HistoryTerm {
+        school_name: "Lincoln High".to_string(),
+        school_year: "2024-2025".to_string(),
+        grade: "10".to_string(),
+        term_name: name.to_string(),
+        courses,
+    }
+}
+
+fn approx(value: Option<f64>, expected: f64) -> bool {
+    value.is_some_and(|value| (value - expected).abs() < 1e-9)
+}
+
+#[test]
+fn test_history_gpa_by_term_and_cumulative() {
+    let history = CourseHistory {
+        terms: vec![
+            term(
+                "Semester 1",
+                vec![
+                    history_course("AP Biology", "A", 1.0, ""),
+                    history_course("English 10", "B", 1.0, "Honors"),
+                    history_course("Health", "P", 0.5, ""),
+                ],
+            ),
+            term(
+                "Semester 2",
+                vec![
+                    history_course("Geometry Hon", "C+", 0.5, ""),
+                    history_course("AP Chemistry", "F", 1.0, ""),
+                ],
+            ),
+        ],
+        gpas: Vec::new(),
+    };
+
+    let report = GpaCalculator::default().history_gpa(&history);
+
+    assert_eq!(report.terms.len(), 2);
+    assert_eq!(report.terms[0].term, "2024-2025 Semester 1");
+    assert!(approx(report.terms[0].gpa.unweighted, 3.5));
+    assert!(approx(report.terms[0].gpa.weighted, 4.25));
+    assert_eq!(report.terms[0].gpa.credits, 2.0);
+
+    // The failed AP course earns no bonus.
+    assert!(approx(report.terms[1].gpa.weighted, (2.8 * 0.5) / 1.5));
+    assert!(approx(
+        report.cumulative.unweighted,
+        (4.0 + 3.0 + 1.15) / 3.5
+    ));
+    assert_eq!(report.cumulative.credits, 3.5);
+}
+
+#[test]
+fn test_gradebook_gpa_with_custom_scale_and_patterns() {
+    let course = |title: &str, scores: &[&str]| Course {
+        period: "1".to_string(),
+        title: title.to_string(),
+        marks: scores
+            .iter()
+            .map(|score| Mark {
+                score: score.to_string(),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let courses = vec![
+        course("Calculus BC", &["B", "A (94%)", ""]),
+        course("Orchestra", &["A"]),
+    ];
+
+    let calculator = GpaCalculator::new(GradeScale::default().with("A", 4.3))
+        .with_level_pattern("calculus bc", CourseLevel::AdvancedPlacement)
+        .with_credits("Orchestra", 0.5);
+
+    let gpa_courses = GpaCourse::from_gradebook(&courses, "Q2");
+    assert_eq!(gpa_courses[0].mark, "A (94%)");
+    assert_eq!(
+        calculator.level_of(&gpa_courses[0]),
+        CourseLevel::AdvancedPlacement
+    );
+    assert_eq!(calculator.level_of(&gpa_courses[1]), CourseLevel::Regular);
+
+    let report = calculator.gradebook_gpa(&courses, "Q2");
+    assert_eq!(report.terms[0].term, "Q2");
+    assert!(approx(report.cumulative.unweighted, 4.3));
+    assert!(approx(report.cumulative.weighted, (5.3 + 4.3 * 0.5) / 1.5));
+    assert!(GpaCalculator::default()
+        .calculate(&[])
+        .cumulative
+        .weighted
+        .is_none());
+}
+
+#[test]
+fn test_level_words_need_a_full_honors_token() {
+    let course = |title: &str| GpaCourse {
+        term: "Q1".to_string(),
+        course_id: String::new(),
+        title: title.to_string(),
+        mark: "A".to_string(),
+        credits: None,
+        course_type: String::new(),
+    };
+
+    let calculator = GpaCalculator::default();
+    assert_eq!(
+        calculator.level_of(&course("Hon Chemistry")),
+        CourseLevel::Honors
+    );
+    assert_eq!(
+        calculator.level_of(&course("Spanish H")),
+        CourseLevel::Regular
+    );
+    assert_eq!(
+        calculator.level_of(&course("Health & PE")),
+        CourseLevel::Regular
+    );
+
+    let calculator = calculator.with_level_pattern("spanish h", CourseLevel::Honors);
+    assert_eq!(
+        calculator.level_of(&course("Spanish H")),
+        CourseLevel::Honors
+    );
+}
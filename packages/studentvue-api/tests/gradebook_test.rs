@@ -0,0 +1,256 @@
+mod common;
+
+use common::MockPortal;
+use studenvue::models::{Assignment, Mark};
+use studenvue::Error;
+
+const NESTED_GRADEBOOK: &str = r#"<Gradebook><Courses>
+<Course Period="1" Title="Algebra II" Room="204" Staff="Lee, Dana" StaffEMail="dlee@example.edu"><Marks>
+<Mark MarkName="Quarter 1" CalculatedScoreString="B+" CalculatedScoreRaw="88.4"><Assignments>
+<Assignment GradebookID="GB-1" Measure="Quiz 1" Type="Quizzes" Date="9/5/2025" DueDate="9/5/2025" Score="9 out of 10.0000" ScoreType="Raw Score" Points="9.00 / 10.0000" Notes="Retake allowed" />
+<Assignment GradebookID="GB-2" Measure="Homework 3" Type="Homework" Date="9/8/2025" DueDate="9/10/2025" Score="Not Graded" ScoreType="Raw Score" Points="5 Points Possible" Notes="" />
+</Assignments></Mark>
+<Mark MarkName="Semester 1" CalculatedScoreString="" />
+</Marks></Course>
+<Course Period="7" Title="Study Hall" Room="Library" Staff="Kim, Alex" StaffEMail="" />
+</Courses></Gradebook>"#;
+
+#[tokio::test]
+async fn test_gradebook_courses_marks_and_assignments() {
+    let portal = MockPortal::new();
+    portal.respond("Gradebook", NESTED_GRADEBOOK);
+    let client = portal.start().await;
+
+    let courses = client.get_gradebook(None).await.unwrap();
+    assert_eq!(courses.len(), 2);
+
+    let algebra = &courses[0];
+    assert_eq!(algebra.period, "1");
+    assert_eq!(algebra.title, "Algebra II");
+    assert_eq!(algebra.room, "204");
+    assert_eq!(algebra.staff, "Lee, Dana");
+    assert_eq!(algebra.staff_email, "dlee@example.edu");
+    assert_eq!(algebra.marks.len(), 2);
+
+    let quarter = &algebra.marks[0];
+    assert_eq!(quarter.mark_name, "Quarter 1");
+    assert_eq!(quarter.score, "B+");
+    assert_eq!(quarter.score_raw.as_deref(), Some("88.4"));
+    assert_eq!(quarter.assignments.len(), 2);
+
+    let quiz = &quarter.assignments[0];
+    assert_eq!(quiz.gradebook_id, "GB-1");
+    assert_eq!(quiz.measure, "Quiz 1");
+    assert_eq!(quiz.assignment_type, "Quizzes");
+    assert_eq!(quiz.date, "9/5/2025");
+    assert_eq!(quiz.due_date, "9/5/2025");
+    assert_eq!(quiz.score, "9 out of 10.0000");
+    assert_eq!(quiz.score_type, "Raw Score");
+    assert_eq!(quiz.points, "9.00 / 10.0000");
+    assert_eq!(quiz.notes, "Retake allowed");
+    assert_eq!(quarter.assignments[1].due_date, "9/10/2025");
+
+    let semester = &algebra.marks[1];
+    assert_eq!(semester.mark_name, "Semester 1");
+    assert!(semester.score_raw.is_none());
+    assert!(semester.assignments.is_empty());
+
+    let study_hall = &courses[1];
+    assert_eq!(study_hall.title, "Study Hall");
+    assert!(study_hall.marks.is_empty());
+}
+
+const GRADEBOOK: &str = r#"<Gradebook><Courses>
+<Course Period="3" Title="Chemistry" Room="112" Staff="Ortiz, Sam" StaffEMail="sortiz@example.edu"><Marks>
+<Mark MarkName="Quarter 1" CalculatedScoreString="B+" CalculatedScoreRaw="88.4">
+<GradeCalculationSummary>
+<AssignmentGradeCalc Type="Labs" Weight="40%" Points="85.00" PointsPossible="100.00" WeightedPct="34.000%" CalculatedMark="B" />
+<AssignmentGradeCalc Type="Tests" Weight="50%" Points="182.00" PointsPossible="200.00" WeightedPct="45.500%" CalculatedMark="A-" />
+<AssignmentGradeCalc Type="Homework" Weight="10%" Points="0.00" PointsPossible="0.00" WeightedPct="0.000%" CalculatedMark="" />
+<AssignmentGradeCalc Type="TOTAL" Weight="100%" Points="267.00" PointsPossible="300.00" WeightedPct="79.500%" CalculatedMark="B+" />
+</GradeCalculationSummary>
+<Assignments>
+<Assignment GradebookID="GB-7" Measure="Lab 1" Type="Labs" Date="9/12/2025" DueDate="9/12/2025" Score="85 out of 100.0000" ScoreType="Raw Score" Points="85.00 / 100.0000" Notes="" />
+</Assignments>
+</Mark>
+</Marks></Course>
+</Courses></Gradebook>"#;
+
+#[tokio::test]
+async fn test_gradebook_grade_categories() {
+    let portal = MockPortal::new();
+    portal.respond("Gradebook", GRADEBOOK);
+    let client = portal.start().await;
+
+    let courses = client.get_gradebook(None).await.unwrap();
+    let mark = &courses[0].marks[0];
+
+    assert_eq!(mark.categories.len(), 4);
+    assert_eq!(mark.assignments.len(), 1);
+
+    let labs = &mark.categories[0];
+    assert_eq!(labs.name, "Labs");
+    assert_eq!(labs.weight, Some(40.0));
+    assert_eq!(labs.points, Some(85.0));
+    assert_eq!(labs.points_possible, Some(100.0));
+    assert_eq!(labs.weighted_percent, Some(34.0));
+    assert_eq!(labs.calculated_mark, "B");
+    assert_eq!(labs.percent(), Some(85.0));
+    assert_eq!(mark.categories[2].percent(), None);
+
+    assert_eq!(mark.weighted_categories().count(), 3);
+    assert_eq!(mark.category_total().unwrap().calculated_mark, "B+");
+
+    // Homework has nothing graded, so labs and tests are scaled to 90%.
+    let percent = mark.calculated_percent().unwrap();
+    assert!((percent - (40.0 * 85.0 + 50.0 * 91.0) / 90.0).abs() < 1e-9);
+}
+
+const STANDARDS_GRADEBOOK: &str = r#"<Gradebook><Courses>
+<Course Period="1" Title="Grade 2 Homeroom" Room="B4" Staff="Nguyen, Lin" StaffEMail=""><Marks>
+<Mark MarkName="Trimester 1" CalculatedScoreString="" CalculatedScoreRaw="">
+<Standards>
+<Standard StandardID="ELA.2.RF.4" Subject="Reading" Description="Reads grade-level text fluently" Proficiency="3" Mark="3 - Meets Standard" ProficiencyMinValue="1" ProficiencyMaxValue="4" />
+<Standard StandardID="MA.2.OA.2" Subject="Math" Description="Adds and subtracts within 20" Proficiency="" Mark="" ProficiencyMinValue="1" ProficiencyMaxValue="4" />
+</Standards>
+<Assignments>
+<Assignment GradebookID="GB-9" Measure="Fluency Check" Type="Reading" Date="10/1/2025" DueDate="10/1/2025" Score="" ScoreType="" Points="" Notes="">
+<Standards><Standard StandardID="ELA.2.RF.4" Description="Reads grade-level text fluently" Proficiency="2" /></Standards>
+</Assignment>
+</Assignments>
+</Mark>
+</Marks></Course>
+</Courses></Gradebook>"#;
+
+#[tokio::test]
+async fn test_gradebook_standards_based_marks() {
+    let portal = MockPortal::new();
+    portal.respond("Gradebook", STANDARDS_GRADEBOOK);
+    let client = portal.start().await;
+
+    let courses = client.get_gradebook(None).await.unwrap();
+    let mark = &courses[0].marks[0];
+
+    assert!(mark.is_standards_based());
+    assert_eq!(mark.standards.len(), 2);
+    assert_eq!(mark.assignments.len(), 1);
+
+    let reading = &mark.standards[0];
+    assert_eq!(reading.standard_id, "ELA.2.RF.4");
+    assert_eq!(reading.subject, "Reading");
+    assert_eq!(reading.description, "Reads grade-level text fluently");
+    assert_eq!(reading.proficiency, Some(3.0));
+    assert_eq!(reading.proficiency_label, "3 - Meets Standard");
+    assert!((reading.scale_position().unwrap() - 2.0 / 3.0).abs() < 1e-9);
+
+    assert!(!mark.standards[1].is_graded());
+
+    let fluency = &mark.assignments[0];
+    assert_eq!(fluency.standards.len(), 1);
+    assert_eq!(fluency.standards[0].standard_id, "ELA.2.RF.4");
+    assert_eq!(fluency.standards[0].proficiency, Some(2.0));
+}
+
+#[test]
+fn test_marks_saved_before_standards_still_load() {
+    let json = r#"{
+        "mark_name": "Quarter 1",
+        "score": "B+",
+        "score_raw": "88.4",
+        "assignments": [{
+            "gradebook_id": "GB-1",
+            "measure": "Quiz 1",
+            "assignment_type": "Quizzes",
+            "date": "9/5/2025",
+            "due_date": "9/5/2025",
+            "score": "9 out of 10.0000",
+            "score_type": "Raw Score",
+            "points": "9.00 / 10.0000",
+            "notes": "",
+            "resources": []
+        }]
+    }"#;
+
+    let mark: Mark = serde_json::from_str(json).unwrap();
+    assert!(mark.categories.is_empty());
+    assert!(mark.standards.is_empty());
+    assert!(mark.assignments[0].standards.is_empty());
+}
+
+const RESOURCES_GRADEBOOK: &str = r#"<Gradebook><Courses>
+<Course Period="4" Title="World History" Room="210" Staff="Kim, Alex" StaffEMail=""><Marks>
+<Mark MarkName="Quarter 1" CalculatedScoreString="A" CalculatedScoreRaw="95">
+<Assignments>
+<Assignment GradebookID="GB-3" Measure="Primary Sources" Type="Classwork" Date="9/15/2025" DueDate="9/19/2025" Score="" ScoreType="" Points="20 Points Possible" Notes="">
+<Resources>
+<Resource ResourceID="RES-1" ResourceName="Worksheet" Type="File" FileName="sources.pdf" URL="" ResourceDescription="Print both pages" />
+<Resource ResourceID="RES-2" ResourceName="Archive" Type="URL" FileName="" URL="https://example.org/archive" ResourceDescription="" />
+</Resources>
+</Assignment>
+</Assignments>
+</Mark>
+</Marks></Course>
+</Courses></Gradebook>"#;
+
+#[tokio::test]
+async fn test_assignment_resources() {
+    let portal = MockPortal::new();
+    portal.respond("Gradebook", RESOURCES_GRADEBOOK).respond(
+        "GetContentOfAttachedDoc",
+        r#"<DocumentData DocumentGU="RES-1" FileName="" DocType="PDF"><Base64Code>JVBERi0xLjQ=</Base64Code></DocumentData>"#,
+    );
+    let client = portal.start().await;
+
+    let courses = client.get_gradebook(None).await.unwrap();
+    let assignment = &courses[0].marks[0].assignments[0];
+
+    assert_eq!(assignment.resources.len(), 2);
+    assert_eq!(assignment.resources[0].description, "Print both pages");
+    assert_eq!(assignment.resources[1].url, "https://example.org/archive");
+
+    let files: Vec<_> = assignment.file_resources().collect();
+    assert_eq!(files.len(), 1);
+
+    let data = client.get_assignment_resource(files[0]).await.unwrap();
+    assert_eq!(data.file_name, "sources.pdf");
+    assert_eq!(data.decode().unwrap().bytes, b"%PDF-1.4");
+
+    let requests = portal.requests_for("GetContentOfAttachedDoc");
+    assert!(requests[0]
+        .params
+        .contains("<DocumentGU>RES-1</DocumentGU>"));
+
+    let mut output = Vec::new();
+    let streamed = client
+        .download_assignment_resource(files[0], &mut output)
+        .await
+        .unwrap();
+    assert_eq!(streamed.file_name, "sources.pdf");
+    assert_eq!(output, b"%PDF-1.4");
+
+    let link = &assignment.resources[1];
+    assert!(matches!(
+        client.get_assignment_resource(link).await,
+        Err(Error::InvalidRequest(_))
+    ));
+}
+
+#[test]
+fn test_assignments_saved_before_resources_still_load() {
+    let json = r#"{
+        "measure": "Primary Sources",
+        "assignment_type": "Classwork",
+        "date": "9/15/2025",
+        "due_date": "9/19/2025",
+        "score": "",
+        "score_type": "",
+        "points": "20 Points Possible",
+        "notes": ""
+    }"#;
+
+    let assignment: Assignment = serde_json::from_str(json).unwrap();
+    assert_eq!(assignment.measure, "Primary Sources");
+    assert!(assignment.gradebook_id.is_empty());
+    assert!(assignment.resources.is_empty());
+    assert_eq!(assignment.file_resources().count(), 0);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::MockPortal;
+
+const COURSE_HISTORY: &str = r#"<CourseHistory>
+    <GPA GPAName="Cumulative Weighted" GPAValue="4.12" />
+    <GPA GPAName="Cumulative Unweighted" GPAValue="3.78" />
+    <CHSYear SchoolName="Lincoln High" Year="2023-2024" Grade="9">
+        <GPA GPAName="Year Weighted" GPAValue="3.95" />
+        <CHSTerm TermName="Semester 1">
+            <CHSCourse CourseID="EN101" CourseTitle="English 9 Honors" Mark="A-" CreditsAttempted="0.5" CreditsCompleted="0.5" CourseType="Honors" SubjectArea="English" />
+            <CHSCourse CourseID="MA201" CourseTitle="Geometry" Mark="B+" CreditsAttempted="0.5" CreditsCompleted="0.5" CourseType="" SubjectArea="Mathematics" />
+        </CHSTerm>
+        <CHSTerm TermName="Semester 2">
+            <CHSCourse CourseID="SC110" CourseTitle="Biology" Mark="F" CreditsAttempted="0.5" CreditsCompleted="0" SubjectArea="Science" />
+            <CHSCourse CourseID="PE100" CourseTitle="Physical Education" Mark="P" CreditsAttempted="" CreditsCompleted="" />
+        </CHSTerm>
+    </CHSYear>
+</CourseHistory>"#;
+
+#[tokio::test]
+async fn test_get_course_history() {
+    let portal = MockPortal::new();
+    portal.respond("CourseHistory", COURSE_HISTORY);
+    let client = portal.start().await;
+
+    let history = client.get_course_history().await.unwrap();
+
+    assert_eq!(history.terms.len(), 2);
+    let first = &history.terms[0];
+    assert_eq!(first.school_name, "Lincoln High");
+    assert_eq!(first.school_year, "2023-2024");
+    assert_eq!(first.grade, "9");
+    assert_eq!(first.term_name, "Semester 1");
+    assert_eq!(first.courses[0].course_type, "Honors");
+    assert_eq!(first.courses[1].mark, "B+");
+
+    assert_eq!(history.credits_attempted(), 1.5);
+    assert_eq!(history.credits_earned(), 1.0);
+    assert_eq!(history.terms[1].courses[1].credits_attempted, None);
+    assert_eq!(history.courses().count(), 4);
+
+    assert_eq!(
+        history.gpa("cumulative weighted").and_then(|gpa| gpa.value),
+        Some(4.12)
+    );
+    assert_eq!(
+        history.gpa("Year Weighted").unwrap().school_year,
+        "2023-2024"
+    );
+    assert_eq!(
+        history.gpa("Cumulative Unweighted").unwrap().school_year,
+        ""
+    );
+}
+
+#[tokio::test]
+async fn test_get_course_history_reports_portal_error() {
+    let client = MockPortal::new().start().await;
+
+    assert!(client.get_course_history().await.is_err());
+}
+
+#[tokio::test]
+async fn test_empty_year_does_not_leak_into_later_gpas() {
+    let portal = MockPortal::new();
+    portal.respond(
+        "CourseHistory",
+        r#"<CourseHistory>
+    <CHSYear SchoolName="Lincoln High" Year="2023-2024" Grade="9">
+        <GPA GPAName="Year Weighted" GPAValue="3.95" />
+    </CHSYear>
+    <CHSYear SchoolName="Lincoln High" Year="2024-2025" Grade="10" />
+    <GPA GPAName="Cumulative Weighted" GPAValue="4.12" />
+</CourseHistory>"#,
+    );
+    let client = portal.start().await;
+
+    let history = client.get_course_history().await.unwrap();
+
+    assert!(history.terms.is_empty());
+    assert_eq!(
+        history.gpa("Year Weighted").unwrap().school_year,
+        "2023-2024"
+    );
+    assert_eq!(history.gpa("Cumulative Weighted").unwrap().school_year, "");
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use chrono::{NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use common::{assignment, course, mark};
+use studenvue::ics::{BellTime, ClassMeetings, IcsCalendar};
+use studenvue::models::{Assignment, CalendarEvent, CalendarEventKind, ClassSchedule, Course};
+
+fn event(date: &str, title: &str, kind: CalendarEventKind) -> CalendarEvent {
+    CalendarEvent {
+        date: date.to_string(),
+        title: title.to_string(),
+        icon: String::new(),
+        day_type: String::new(),
+        start_time: String::new(),
+        link: String::new(),
+        kind,
+    }
+}
+
+fn algebra() -> Course {
+    let quiz = Assignment {
+        assignment_type: "Quizzes".to_string(),
+        ..assignment("GB-42", "Quiz 2", "10/3/2025", "", "10 Points Possible")
+    };
+    Course {
+        room: "204".to_string(),
+        ..course(
+            "2",
+            "Algebra II",
+            "Lee, Dana",
+            vec![mark("Quarter 1", "B+", vec![quiz])],
+        )
+    }
+}
+
+fn calendar() -> IcsCalendar {
+    IcsCalendar::new("Student, Test")
+        .with_timestamp(Utc.with_ymd_and_hms(2025, 10, 1, 12, 0, 0).unwrap())
+}
+
+fn export_events_and_assignments() -> String {
+    let mut ics = calendar();
+    ics.add_calendar_events(&[
+        event(
+            "10/13/2025",
+            "No School; Staff Development",
+            CalendarEventKind::Holiday,
+        ),
+        event(
+            "10/3/2025",
+            "Algebra II: Quiz 2",
+            CalendarEventKind::Assignment {
+                assignment_gu: "GB-42".to_string(),
+                gradebook_gu: String::new(),
+                view_type: String::new(),
+            },
+        ),
+    ])
+    .add_assignments(&[algebra()]);
+    ics.to_ics()
+}
+
+#[test]
+fn test_export_events_and_assignments() {
+    let output = export_events_and_assignments();
+
+    assert!(output.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(output.ends_with("END:VCALENDAR\r\n"));
+    assert!(output.contains("X-WR-CALNAME:Student\\, Test\r\n"));
+    assert!(output.contains("SUMMARY:No School\\; Staff Development\r\n"));
+    assert!(output.contains("DTSTART;VALUE=DATE:20251013\r\nDTEND;VALUE=DATE:20251014\r\n"));
+    assert!(output.contains("DTSTAMP:20251001T120000Z\r\n"));
+    assert_eq!(output.matches("UID:assignment-GB-42@studentvue").count(), 1);
+    assert_eq!(output.matches("BEGIN:VEVENT").count(), 2);
+    assert!(output.lines().all(|line| line.len() <= 75));
+}
+
+#[test]
+fn test_export_uids_are_stable() {
+    assert_eq!(
+        export_events_and_assignments(),
+        export_events_and_assignments()
+    );
+}
+
+#[test]
+fn test_export_recurring_class_meetings() {
+    let schedule = vec![ClassSchedule {
+        period: "2".to_string(),
+        course_title: "Algebra II".to_string(),
+        room_name: "204".to_string(),
+        teacher: "Lee, Dana".to_string(),
+        teacher_email: String::new(),
+    }];
+    let meetings = ClassMeetings {
+        first_day: NaiveDate::from_ymd_opt(2025, 8, 23).unwrap(),
+        last_day: NaiveDate::from_ymd_opt(2026, 6, 12).unwrap(),
+        weekdays: vec![Weekday::Mon, Weekday::Wed, Weekday::Fri],
+        bell_times: vec![BellTime {
+            period: "2".to_string(),
+            start: NaiveTime::from_hms_opt(9, 5, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(9, 55, 0).unwrap(),
+        }],
+        skip_dates: vec![NaiveDate::from_ymd_opt(2025, 10, 13).unwrap()],
+    };
+
+    let mut ics = calendar();
+    ics.add_class_meetings(&schedule, &meetings);
+    let output = ics.to_ics();
+
+    assert!(output.contains("DTSTART:20250825T090500\r\nDTEND:20250825T095500\r\n"));
+    assert!(output.contains("RRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR;UNTIL=20260612T095500\r\n"));
+    assert!(output.contains("EXDATE:20251013T090500\r\n"));
+    assert!(output.contains("LOCATION:204\r\n"));
+}
@@ -22,6 +22,9 @@ async fn test_get_messages() {
             println!("✓ Found {} messages", messages.len());
             for (i, msg) in messages.iter().take(3).enumerate() {
                 println!("  Message {}: {} (from: {})", i + 1, msg.subject, msg.from);
+                for attachment in &msg.attachments {
+                    println!("    Attachment: {}", attachment.document_name);
+                }
             }
         }
         Err(e) => println!("✗ Error getting messages: {}", e),
@@ -88,6 +91,49 @@ async fn test_get_attendance() {
     }
 }
 
+#[tokio::test]
+async fn test_get_attendance_report() {
+    let (portal, username, password) = get_test_credentials();
+    let client = StudentVueClient::new(portal, username, password);
+
+    let result = client.get_attendance_report().await;
+    match result {
+        Ok(report) => {
+            println!(
+                "✓ Attendance for {} ({} periods)",
+                report.school_name, report.period_count
+            );
+            println!("  Excused: {}", report.total_excused.sum());
+            println!("  Unexcused: {}", report.total_unexcused.sum());
+            println!("  Tardies: {}", report.total_tardies.sum());
+            println!("  Activities: {}", report.total_activities.sum());
+        }
+        Err(e) => println!("✗ Error getting attendance report: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn test_get_course_history() {
+    let (portal, username, password) = get_test_credentials();
+    let client = StudentVueClient::new(portal, username, password);
+
+    let result = client.get_course_history().await;
+    match result {
+        Ok(history) => {
+            println!("✓ Found {} course history terms", history.terms.len());
+            println!(
+                "  Credits: {} earned of {} attempted",
+                history.credits_earned(),
+                history.credits_attempted()
+            );
+            for gpa in &history.gpas {
+                println!("  {}: {:?}", gpa.name, gpa.value);
+            }
+        }
+        Err(e) => println!("✗ Error getting course history: {}", e),
+    }
+}
+
 #[tokio::test]
 async fn test_get_calendar() {
     let (portal, username, password) = get_test_credentials();
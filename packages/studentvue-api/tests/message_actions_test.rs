@@ -0,0 +1,95 @@
+mod common;
+
+use common::MockPortal;
+use studenvue::models::{Message, MessageAction, MessageSender};
+
+fn message(id: &str, deletable: bool) -> Message {
+    Message {
+        id: id.to_string(),
+        subject: String::new(),
+        subject_no_html: String::new(),
+        content: String::new(),
+        from: String::new(),
+        sender: MessageSender {
+            name: String::new(),
+            person_gu: String::new(),
+            email: String::new(),
+        },
+        date: String::new(),
+        read: false,
+        deletable,
+        message_type: "StudentActivity".to_string(),
+        module_name: String::new(),
+        attachments: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn test_mark_read_and_unread() {
+    let portal = MockPortal::new();
+    portal.respond("UpdatePXPMessage", "<UpdatePXPMessageResult />");
+    let client = portal.start().await;
+
+    let read = client
+        .mark_message_read("MSG-1", "StudentActivity")
+        .await
+        .unwrap();
+    assert!(read.success);
+    assert_eq!(read.action, MessageAction::MarkRead);
+
+    let unread = client
+        .mark_message_unread("MSG-1", "StudentActivity")
+        .await
+        .unwrap();
+    assert!(unread.success);
+
+    let requests = portal.requests_for("UpdatePXPMessage");
+    assert!(requests[0]
+        .params
+        .contains(r#"ID="MSG-1" Type="StudentActivity" MarkAsRead="true""#));
+    assert!(requests[1].params.contains(r#"MarkAsRead="false""#));
+    assert!(requests[0]
+        .soap_action
+        .contains("ProcessWebServiceRequestMultiWeb"));
+}
+
+#[tokio::test]
+async fn test_bulk_delete_honors_deletable() {
+    let portal = MockPortal::new();
+    portal.respond("UpdatePXPMessage", "<UpdatePXPMessageResult />");
+    let client = portal.start().await;
+
+    let results = client
+        .apply_message_actions(
+            &[message("MSG-1", true), message("MSG-2", false)],
+            MessageAction::Delete,
+        )
+        .await;
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].success);
+    assert!(!results[1].success);
+    assert_eq!(results[1].message_id, "MSG-2");
+
+    let requests = portal.requests_for("UpdatePXPMessage");
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].params.contains(r#"ID="MSG-1""#));
+    assert!(requests[0].params.contains(r#"Delete="true""#));
+}
+
+#[tokio::test]
+async fn test_portal_rejection_is_reported_per_message() {
+    let portal = MockPortal::new();
+    portal.respond(
+        "UpdatePXPMessage",
+        r#"<RT_ERROR ERROR_MESSAGE="Message not found" />"#,
+    );
+    let client = portal.start().await;
+
+    let results = client
+        .apply_message_actions(&[message("MSG-404", true)], MessageAction::MarkRead)
+        .await;
+
+    assert!(!results[0].success);
+    assert_eq!(results[0].error.as_deref(), Some("Message not found"));
+}
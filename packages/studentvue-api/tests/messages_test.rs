@@ -0,0 +1,62 @@
+mod common;
+
+use common::MockPortal;
+use studenvue::models::Message;
+
+const MESSAGES: &str = r#"<PXPMessagesData><MessageListings>
+<MessageListing ID="MSG-1" Type="StudentActivity" BeginDate="10/14/2025 8:15:00 AM" Subject="&lt;b&gt;Field trip&lt;/b&gt;" SubjectNoHTML="Field trip" Content="&lt;p&gt;Forms are due Friday.&lt;/p&gt;" Read="false" Deletable="true" From="Lee, Dana" SMMsgPersonGU="STAFF-1" Email="dlee@lincoln.edu" ModuleName="Synergy Mail">
+<AttachmentDatas>
+<AttachmentData SmAttachmentGU="ATT-1" DocumentName="permission.pdf" />
+<AttachmentData SmAttachmentGU="ATT-2" DocumentName="itinerary.docx" />
+</AttachmentDatas>
+</MessageListing>
+<MessageListing ID="MSG-2" Type="Notification" BeginDate="10/15/2025" Subject="Grades posted" SubjectNoHTML="Grades posted" Content="" Read="true" Deletable="false" From="Lincoln High" ModuleName="Gradebook" />
+</MessageListings></PXPMessagesData>"#;
+
+#[tokio::test]
+async fn test_get_messages_with_sender_and_attachments() {
+    let portal = MockPortal::new();
+    portal.respond("GetPXPMessages", MESSAGES);
+    let client = portal.start().await;
+
+    let messages = client.get_messages().await.unwrap();
+    assert_eq!(messages.len(), 2);
+
+    let trip = &messages[0];
+    assert_eq!(trip.subject_no_html, "Field trip");
+    assert_eq!(trip.from, "Lee, Dana");
+    assert_eq!(trip.sender.name, "Lee, Dana");
+    assert_eq!(trip.sender.person_gu, "STAFF-1");
+    assert_eq!(trip.sender.email, "dlee@lincoln.edu");
+    assert_eq!(trip.module_name, "Synergy Mail");
+    assert!(!trip.read);
+    assert!(trip.deletable);
+    assert_eq!(trip.attachments.len(), 2);
+    assert_eq!(trip.attachments[1].attachment_gu, "ATT-2");
+    assert_eq!(trip.attachments[1].document_name, "itinerary.docx");
+
+    let notice = &messages[1];
+    assert_eq!(notice.from, "Lincoln High");
+    assert!(notice.sender.email.is_empty());
+    assert!(notice.attachments.is_empty());
+    assert!(!notice.deletable);
+}
+
+#[test]
+fn test_messages_saved_before_sender_still_load() {
+    let json = r#"{
+        "id": "MSG-1",
+        "subject": "Field trip",
+        "content": "",
+        "from": "Lee, Dana",
+        "date": "10/14/2025",
+        "read": false,
+        "deletable": true,
+        "message_type": "StudentActivity"
+    }"#;
+
+    let message: Message = serde_json::from_str(json).unwrap();
+    assert_eq!(message.id, "MSG-1");
+    assert_eq!(message.from, "Lee, Dana");
+    assert!(message.attachments.is_empty());
+}
@@ -0,0 +1,53 @@
+use studenvue::render::{decode_entities, html_to_markdown, html_to_text};
+
+const BODY: &str = r#"<html><head><style>p { color: red; }</style></head><body>
+<p style="font-family: Arial">Hi <b>class</b>,&nbsp;the quiz is on <em>Friday</em> &amp; covers ch.&#160;3.</p>
+<script>alert('x')</script>
+<ul><li>Review <a href="https://example.com/notes?a=1&amp;b=2">the notes</a></li><li>Bring a calculator</li></ul>
+<p>Questions? Email <a href="mailto:teacher@example.com">teacher@example.com</a><br>Thanks!</p>
+</body></html>"#;
+
+#[test]
+fn test_html_to_text() {
+    assert_eq!(
+        html_to_text(BODY),
+        "Hi class, the quiz is on Friday & covers ch. 3.\n\n\
+         - Review the notes (https://example.com/notes?a=1&b=2)\n\
+         - Bring a calculator\n\n\
+         Questions? Email teacher@example.com\n\
+         Thanks!"
+    );
+}
+
+#[test]
+fn test_html_to_markdown() {
+    assert_eq!(
+        html_to_markdown(BODY),
+        "Hi **class**, the quiz is on _Friday_ & covers ch. 3.\n\n\
+         - Review [the notes](https://example.com/notes?a=1&b=2)\n\
+         - Bring a calculator\n\n\
+         Questions? Email [teacher@example.com](mailto:teacher@example.com)\n\
+         Thanks!"
+    );
+}
+
+#[test]
+fn test_entity_encoded_markup_is_decoded() {
+    assert_eq!(
+        html_to_text("&lt;p&gt;See you &lt;b&gt;Monday&lt;/b&gt;&lt;/p&gt;"),
+        "See you Monday"
+    );
+    assert_eq!(
+        decode_entities("a &amp;&amp; b &unknown; &#x41;"),
+        "a && b &unknown; A"
+    );
+}
+
+#[test]
+fn test_non_ascii_text() {
+    assert_eq!(
+        html_to_text("<p>élan</p><p>“Quoted” café</p>"),
+        "élan\n\n“Quoted” café"
+    );
+    assert_eq!(html_to_markdown("<b>ñandú</b> – día"), "**ñandú** – día");
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::MockPortal;
+use studenvue::models::{Gradebook, ReportPeriod};
+
+fn period(index: usize, name: &str, start: &str, end: &str) -> ReportPeriod {
+    ReportPeriod {
+        index: Some(index),
+        name: name.to_string(),
+        start_date: parse(start),
+        end_date: parse(end),
+    }
+}
+
+fn parse(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%m/%d/%Y").ok()
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_reporting_period_lookup() {
+    let gradebook = Gradebook {
+        reporting_periods: vec![
+            period(0, "Quarter 1", "8/25/2025", "10/31/2025"),
+            period(1, "Quarter 2", "11/3/2025", "12/19/2025"),
+            period(2, "Quarter 3", "1/5/2026", "3/20/2026"),
+        ],
+        reporting_period: None,
+        courses: Vec::new(),
+    };
+
+    let name = |period: Option<&ReportPeriod>| period.map(|period| period.name.clone());
+
+    assert_eq!(
+        name(gradebook.reporting_period_for(date(2025, 10, 31))),
+        Some("Quarter 1".to_string())
+    );
+    assert_eq!(
+        name(gradebook.reporting_period_for(date(2025, 12, 25))),
+        None
+    );
+
+    // Winter break stays on the quarter that just ended.
+    assert_eq!(
+        name(gradebook.reporting_period_on(date(2025, 12, 25))),
+        Some("Quarter 2".to_string())
+    );
+    assert_eq!(
+        name(gradebook.reporting_period_on(date(2025, 8, 1))),
+        Some("Quarter 1".to_string())
+    );
+    assert_eq!(
+        name(gradebook.reporting_period_on(date(2026, 7, 1))),
+        Some("Quarter 3".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_reporting_periods_from_gradebook() {
+    let portal = MockPortal::new();
+    portal.respond(
+        "Gradebook",
+        r#"<Gradebook><ReportingPeriods>
+<ReportPeriod Index="0" GradePeriod="Semester 1" StartDate="8/25/2025" EndDate="1/23/2026" />
+<ReportPeriod Index="1" GradePeriod="Semester 2" StartDate="1/26/2026" EndDate="6/12/2026" />
+</ReportingPeriods><ReportingPeriod GradePeriod="Semester 1" StartDate="8/25/2025" EndDate="1/23/2026" /><Courses /></Gradebook>"#,
+    );
+    let client = portal.start().await;
+
+    let periods = client.get_reporting_periods().await.unwrap();
+    assert_eq!(periods.len(), 2);
+    assert_eq!(periods[1].index, Some(1));
+    assert_eq!(periods[1].start_date, Some(date(2026, 1, 26)));
+    assert_eq!(periods[1].end_date, Some(date(2026, 6, 12)));
+
+    let spring = client
+        .reporting_period_for(date(2026, 3, 2))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(spring.name, "Semester 2");
+
+    let listing = client.get_gradebook_listing(None).await.unwrap();
+    assert_eq!(
+        listing.reporting_period.as_ref().unwrap().name,
+        "Semester 1"
+    );
+
+    let name = |on: NaiveDate| {
+        listing
+            .reporting_period_on(on)
+            .map(|period| period.name.as_str())
+    };
+    assert_eq!(name(date(2025, 9, 2)), Some("Semester 1"));
+    assert_eq!(name(date(2026, 1, 24)), Some("Semester 1"));
+    assert_eq!(name(date(2026, 1, 26)), Some("Semester 2"));
+    assert_eq!(name(date(2026, 7, 1)), Some("Semester 2"));
+}
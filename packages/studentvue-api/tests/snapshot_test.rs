@@ -0,0 +1,188 @@
+mod common;
+
+use common::MockPortal;
+use studenvue::snapshot::{Snapshot, SnapshotEndpoint};
+
+const PERIODS: &str = r#"<ReportingPeriods>
+<ReportPeriod Index="0" GradePeriod="Quarter 1" StartDate="8/25/2025" EndDate="10/31/2025" />
+<ReportPeriod Index="1" GradePeriod="Quarter 2" StartDate="11/3/2025" EndDate="1/23/2026" />
+<ReportPeriod Index="2" GradePeriod="Quarter 3" StartDate="1/26/2026" EndDate="3/27/2026" />
+</ReportingPeriods>"#;
+
+fn gradebook(period: &str, score: &str) -> String {
+    format!(
+        r#"<Gradebook>{PERIODS}<ReportingPeriod GradePeriod="{period}" /><Courses>
+<Course Period="1" Title="Algebra II" Room="101" Staff="Dana Lee" StaffEMail="dlee@lincoln.edu">
+<Marks><Mark MarkName="{period}" CalculatedScoreString="B" CalculatedScoreRaw="{score}" /></Marks>
+</Course>
+</Courses></Gradebook>"#
+    )
+}
+
+fn portal() -> MockPortal {
+    portal_with_third_quarter(&gradebook("Quarter 3", "88"))
+}
+
+fn portal_with_third_quarter(xml: &str) -> MockPortal {
+    let portal = MockPortal::new();
+    portal
+        .respond("Gradebook", &gradebook("Quarter 2", "85"))
+        .respond_matching(
+            "Gradebook",
+            "<ReportPeriod>0</ReportPeriod>",
+            &gradebook("Quarter 1", "91"),
+        )
+        .respond_matching(
+            "Gradebook",
+            "<ReportPeriod>2</ReportPeriod>",
+            xml,
+        )
+        .respond(
+            "StudentInfo",
+            "<StudentInfo><FormattedName>Sam Rivera</FormattedName></StudentInfo>",
+        )
+        .respond(
+            "StudentClassList",
+            r#"<StudentClassSchedule><ClassLists>
+<ClassListing Period="1" CourseTitle="Algebra II" RoomName="101" Teacher="Dana Lee" TeacherEmail="dlee@lincoln.edu" />
+</ClassLists></StudentClassSchedule>"#,
+        )
+        .respond(
+            "Attendance",
+            r#"<Attendance Type="Period" SchoolName="Lincoln High" PeriodCount="7"><Absences /></Attendance>"#,
+        )
+        .respond(
+            "GetPXPMessages",
+            r#"<PXPMessagesData><MessageListings>
+<MessageListing ID="MSG-1" Type="StudentActivity" BeginDate="10/14/2025" Subject="Field trip" SubjectNoHTML="Field trip" Content="" Read="false" Deletable="true" From="Lee, Dana" />
+</MessageListings></PXPMessagesData>"#,
+        )
+        .respond(
+            "StudentCalendar",
+            r#"<CalendarListing SchoolBegDate="8/25/2025" SchoolEndDate="6/12/2026" MonthBegDate="10/1/2025"><EventLists /></CalendarListing>"#,
+        )
+        .respond(
+            "GetStudentDocumentInitialData",
+            r#"<StudentDocuments><StudentDocumentDatas>
+<StudentDocumentData DocumentGU="DOC-1" DocumentFileName="Transcript.pdf" DocumentDate="6/1/2025" DocumentType="Transcript" DocumentComment="" />
+</StudentDocumentDatas></StudentDocuments>"#,
+        )
+        .respond(
+            "GetReportCardInitialData",
+            r#"<RCReportingPeriodData><RCReportingPeriods>
+<RCReportingPeriod DocumentGU="RC-1" ReportingPeriodName="Quarter 1" EndDate="10/31/2025" Message="" />
+</RCReportingPeriods></RCReportingPeriodData>"#,
+        )
+        .respond("StudentHealthInfo", "<StudentHealthData />");
+    portal
+}
+
+#[tokio::test]
+async fn test_snapshot_fetches_every_endpoint() {
+    let portal = portal();
+    let client = portal.start().await;
+
+    let snapshot = client.snapshot().await.unwrap();
+
+    assert_eq!(snapshot.student_info.as_ref().unwrap().name, "Sam Rivera");
+    assert_eq!(snapshot.schedule.as_ref().unwrap().len(), 1);
+    assert!(snapshot.attendance.is_some());
+    assert_eq!(snapshot.messages.as_ref().unwrap()[0].id, "MSG-1");
+    assert!(snapshot.calendar.is_some());
+    assert_eq!(snapshot.documents.as_ref().unwrap()[0].document_gu, "DOC-1");
+    assert_eq!(
+        snapshot.report_cards.as_ref().unwrap()[0].document_gu,
+        "RC-1"
+    );
+    assert!(snapshot.health.is_some());
+
+    let periods: Vec<String> = snapshot
+        .gradebooks
+        .iter()
+        .map(|gradebook| gradebook.reporting_period.as_ref().unwrap().name.clone())
+        .collect();
+    assert_eq!(periods, ["Quarter 1", "Quarter 2", "Quarter 3"]);
+    assert_eq!(
+        snapshot.gradebook(2).unwrap().courses[0].marks[0].score_raw,
+        Some("88".to_string())
+    );
+    // The default gradebook already shows Quarter 2, so it is not fetched twice.
+    assert_eq!(portal.requests_for("Gradebook").len(), 3);
+}
+
+#[tokio::test]
+async fn test_snapshot_collects_errors() {
+    // StudentSchoolInfo is not registered, so the mock answers with RT_ERROR.
+    let portal = portal_with_third_quarter("<Gradebook></Courses>");
+    let client = portal.start().await;
+
+    let snapshot = client.snapshot().await.unwrap();
+
+    assert!(!snapshot.is_complete());
+    assert!(snapshot.school_info.is_none());
+    assert!(snapshot.failed(SnapshotEndpoint::SchoolInfo));
+    assert!(!snapshot.failed(SnapshotEndpoint::Messages));
+    assert!(snapshot.errors.iter().any(|error| {
+        error.endpoint == SnapshotEndpoint::Gradebook && error.reporting_period == Some(2)
+    }));
+    assert!(snapshot.student_info.is_some());
+    assert_eq!(snapshot.gradebooks.len(), 2);
+}
+
+#[tokio::test]
+async fn test_snapshot_concurrency_limit() {
+    let portal = portal().with_chunk_size(64);
+    let client = portal.start().await;
+
+    let snapshot = client.snapshot_with_concurrency(2).await.unwrap();
+
+    assert_eq!(snapshot.gradebooks.len(), 3);
+    assert!(portal.max_in_flight() <= 2);
+    assert!(portal.max_in_flight() > 1);
+}
+
+#[tokio::test]
+async fn test_snapshot_round_trips_through_json() {
+    let client = portal().start().await;
+    let snapshot = client.snapshot().await.unwrap();
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let restored: Snapshot = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored.taken_at, snapshot.taken_at);
+    assert_eq!(restored.gradebooks.len(), 3);
+    assert_eq!(restored.errors.len(), snapshot.errors.len());
+}
+
+#[tokio::test]
+async fn test_snapshot_retries_default_gradebook() {
+    let portal = portal();
+    portal.fail_next("Gradebook", 1);
+    let client = portal.start().await;
+
+    let snapshot = client.snapshot().await.unwrap();
+
+    assert!(!snapshot.failed(SnapshotEndpoint::Gradebook));
+    assert_eq!(snapshot.gradebooks.len(), 3);
+    assert_eq!(portal.requests_for("Gradebook").len(), 4);
+}
+
+#[tokio::test]
+async fn test_snapshot_without_reporting_periods_fails_every_gradebook() {
+    let portal = portal();
+    portal.fail_next("Gradebook", 2);
+    let client = portal.start().await;
+
+    let snapshot = client.snapshot().await.unwrap();
+
+    assert!(snapshot.gradebooks.is_empty());
+    let errors: Vec<_> = snapshot
+        .errors
+        .iter()
+        .filter(|error| error.endpoint == SnapshotEndpoint::Gradebook)
+        .collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].reporting_period, None);
+    assert_eq!(portal.requests_for("Gradebook").len(), 2);
+    assert!(snapshot.student_info.is_some());
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use common::MockPortal;
+use studenvue::models::{ClassSchedule, Course, SchoolInfo};
+use studenvue::student::StudentCourse;
+
+const STUDENT_INFO: &str = r#"<StudentInfo><FormattedName>Sam Rivera</FormattedName><PermID>123456</PermID><Grade>10</Grade><CurrentSchool>Lincoln High</CurrentSchool></StudentInfo>"#;
+
+const SCHOOL_INFO: &str = r#"<StudentSchoolInfoListing School="Lincoln High" Principal="Pat Morgan" Phone="555-0100">
+<StaffLists>
+<StaffList Name="Dana Lee" EMail="dlee@lincoln.edu" Title="Teacher" Phone="555-0101" Extn="201" StaffGU="S-1" />
+<StaffList Name="Chris Ortiz" EMail="" Title="Teacher" Phone="555-0102" Extn="202" StaffGU="S-2" />
+<StaffList Name="Morgan Park" EMail="mpark@lincoln.edu" Title="Counselor" Phone="555-0103" Extn="" StaffGU="S-3" />
+</StaffLists>
+</StudentSchoolInfoListing>"#;
+
+const GRADEBOOK: &str = r#"<Gradebook><Courses>
+<Course Period="1" Title="Algebra II" Room="101" Staff="Lee, Dana" StaffEMail="">
+<Marks><Mark MarkName="Q1" CalculatedScoreString="B+" CalculatedScoreRaw="88.5">
+<Assignments>
+<Assignment GradebookID="A1" Measure="Quiz 1" Type="Quizzes" Date="9/5/2025" DueDate="9/5/2025" Score="9 out of 10" Points="9 / 10" Notes="" />
+<Assignment GradebookID="A2" Measure="Homework 1" Type="Homework" Date="9/8/2025" DueDate="9/8/2025" Score="Not Graded" Points="10 Points Possible" Notes="" />
+</Assignments>
+</Mark>
+<Mark MarkName="S1" CalculatedScoreString="B+" CalculatedScoreRaw="88.5">
+<Assignments>
+<Assignment GradebookID="A1" Measure="Quiz 1" Type="Quizzes" Date="9/5/2025" DueDate="9/5/2025" Score="9 out of 10" Points="9 / 10" Notes="" />
+</Assignments>
+</Mark></Marks>
+</Course>
+<Course Period="02" Title="Chemistry" Room="210" Staff="Chris Ortiz" StaffEMail="">
+<Marks><Mark MarkName="Q1" CalculatedScoreString="A" CalculatedScoreRaw="95" /></Marks>
+</Course>
+</Courses></Gradebook>"#;
+
+const SCHEDULE: &str = r#"<StudentClassSchedule><ClassLists>
+<ClassListing Period="1" CourseTitle="Algebra II" RoomName="B-101" Teacher="Dana Lee" TeacherEmail="dlee@lincoln.edu" />
+<ClassListing Period="2" CourseTitle="Chemistry" RoomName="S-210" Teacher="Chris Ortiz" TeacherEmail="" />
+<ClassListing Period="3" CourseTitle="Study Hall" RoomName="Library" Teacher="Morgan Park" TeacherEmail="" />
+</ClassLists></StudentClassSchedule>"#;
+
+const ATTENDANCE: &str = r#"<Attendance Type="Period" SchoolName="Lincoln High" PeriodCount="3">
+<Absences>
+<Absence AbsenceDate="9/10/2025" Reason="Illness" Note="">
+<Periods>
+<Period Number="1" Name="Period 1" Reason="Excused" Course="Algebra II" Staff="Lee, Dana" StaffEMail="" />
+<Period Number="3" Name="Period 3" Reason="Excused" Course="Study Hall" Staff="Park, Morgan" StaffEMail="" />
+</Periods>
+</Absence>
+<Absence AbsenceDate="9/12/2025" Reason="Tardy" Note="">
+<Periods>
+<Period Number="2" Name="Period 2" Reason="Tardy" Course="Chemistry" Staff="Ortiz, Chris" StaffEMail="" />
+</Periods>
+</Absence>
+<Absence AbsenceDate="9/15/2025" Reason="Field Trip" Note="">
+<Periods>
+<Period Number="2" Name="Period 2" Reason="Activity" Course="" Staff="" StaffEMail="" />
+<Period Number="3" Name="Period 3" Reason="Activity" Course="Band" Staff="Reyes, Lou" StaffEMail="" />
+</Periods>
+</Absence>
+</Absences>
+</Attendance>"#;
+
+fn portal() -> MockPortal {
+    let portal = MockPortal::new();
+    portal
+        .respond("StudentInfo", STUDENT_INFO)
+        .respond("StudentSchoolInfo", SCHOOL_INFO)
+        .respond("Gradebook", GRADEBOOK)
+        .respond("StudentClassList", SCHEDULE)
+        .respond("Attendance", ATTENDANCE);
+    portal
+}
+
+#[tokio::test]
+async fn test_school_info_staff() {
+    let client = portal().start().await;
+
+    let school = client.get_school_info().await.unwrap();
+    assert_eq!(school.school, "Lincoln High");
+    assert_eq!(school.principal, "Pat Morgan");
+    assert_eq!(school.staff.len(), 3);
+    assert_eq!(school.staff[0].name, "Dana Lee");
+    assert_eq!(school.staff[0].email, "dlee@lincoln.edu");
+    assert_eq!(school.staff[0].extension, "201");
+    assert_eq!(school.staff[2].title, "Counselor");
+}
+
+#[tokio::test]
+async fn test_student_links_courses() {
+    let client = portal().start().await;
+
+    let student = client.get_student().await.unwrap();
+    assert_eq!(student.info.name, "Sam Rivera");
+    assert_eq!(student.courses.len(), 3);
+
+    let algebra = student.course("algebra ii").unwrap();
+    assert_eq!(algebra.schedule.as_ref().unwrap().room_name, "B-101");
+    assert_eq!(algebra.room(), "B-101");
+    // The gradebook has no email, so the staff entry is found by name.
+    assert_eq!(algebra.teacher.as_ref().unwrap().staff_gu, "S-1");
+    assert_eq!(algebra.teacher_email(), "dlee@lincoln.edu");
+    assert_eq!(algebra.absences.len(), 1);
+    assert_eq!(algebra.absences[0].date, "9/10/2025");
+    // Quiz 1 appears under both Q1 and S1.
+    assert_eq!(algebra.assignments().len(), 2);
+
+    let chemistry = student.course_for_period("2").unwrap();
+    assert_eq!(chemistry.title, "Chemistry");
+    assert_eq!(chemistry.period, "02");
+    assert_eq!(chemistry.teacher.as_ref().unwrap().phone, "555-0102");
+    assert_eq!(chemistry.absences[0].period.reason, "Tardy");
+    // No course name, so the period number is all there is to go on.
+    assert_eq!(chemistry.absences.len(), 2);
+    assert_eq!(chemistry.absences[1].date, "9/15/2025");
+
+    // Scheduled without a gradebook entry.
+    let study_hall = student.course_for_period("3").unwrap();
+    assert!(study_hall.gradebook.is_none());
+    assert_eq!(study_hall.teacher.as_ref().unwrap().title, "Counselor");
+    // Band meets in period 3 on other days; it is not Study Hall.
+    assert_eq!(study_hall.absences.len(), 1);
+    assert!(study_hall.assignments().is_empty());
+
+    assert!(student.course("Biology").is_none());
+}
+
+#[test]
+fn test_blank_course_details_fall_back_to_other_endpoints() {
+    let course = StudentCourse {
+        period: "1".to_string(),
+        title: "Algebra II".to_string(),
+        gradebook: Some(Course {
+            staff: String::new(),
+            room: "101".to_string(),
+            ..Default::default()
+        }),
+        schedule: Some(ClassSchedule {
+            period: "1".to_string(),
+            course_title: "Algebra II".to_string(),
+            room_name: String::new(),
+            teacher: "Lee, Dana".to_string(),
+            teacher_email: String::new(),
+        }),
+        teacher: None,
+        absences: Vec::new(),
+    };
+
+    assert_eq!(course.teacher_name(), "Lee, Dana");
+    assert_eq!(course.room(), "101");
+}
+
+#[test]
+fn test_school_info_saved_before_staff_still_loads() {
+    let json = r#"{
+        "school": "Lincoln High",
+        "principal": "Pat Morgan",
+        "address": "",
+        "city": "",
+        "state": "",
+        "zip": "",
+        "phone": "555-0100",
+        "url": ""
+    }"#;
+
+    let school: SchoolInfo = serde_json::from_str(json).unwrap();
+    assert_eq!(school.principal, "Pat Morgan");
+    assert!(school.staff.is_empty());
+}
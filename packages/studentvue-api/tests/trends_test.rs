@@ -0,0 +1,121 @@
+mod common;
+
+use common::MockPortal;
+use studenvue::GradeTrendAnalysis;
+
+const REPORT_PERIODS: &str = r#"<ReportingPeriods>
+<ReportPeriod Index="0" GradePeriod="Quarter 1" StartDate="8/25/2025" EndDate="10/31/2025" />
+<ReportPeriod Index="1" GradePeriod="Quarter 2" StartDate="11/3/2025" EndDate="1/23/2026" />
+<ReportPeriod Index="2" GradePeriod="Quarter 3" StartDate="1/26/2026" EndDate="4/3/2026" />
+</ReportingPeriods>"#;
+
+fn gradebook(period: &str, courses: &[(&str, &str, &str, &str)]) -> String {
+    let courses: String = courses
+        .iter()
+        .map(|(number, title, staff, raw)| {
+            format!(
+                r#"<Course Period="{number}" Title="{title}" Room="" Staff="{staff}" StaffEMail=""><Marks><Mark MarkName="{period}" CalculatedScoreString="" CalculatedScoreRaw="{raw}" /></Marks></Course>"#
+            )
+        })
+        .collect();
+    format!(
+        r#"<Gradebook>{REPORT_PERIODS}<ReportingPeriod GradePeriod="{period}" StartDate="" EndDate="" /><Courses>{courses}</Courses></Gradebook>"#
+    )
+}
+
+#[tokio::test]
+async fn test_grade_trends_across_reporting_periods() {
+    let portal = MockPortal::new();
+    portal
+        .respond(
+            "Gradebook",
+            &gradebook("Quarter 2", &[("1", "Algebra II", "Lee, Dana", "85")]),
+        )
+        .respond_matching(
+            "Gradebook",
+            "<ReportPeriod>0</ReportPeriod>",
+            &gradebook(
+                "Quarter 1",
+                &[
+                    ("1", "Algebra II", "Lee, Dana", "92.5"),
+                    ("2", "Chemistry", "Ortiz, Sam", "78"),
+                    ("3", "Spanish II", "Diaz, Ana", "88"),
+                ],
+            ),
+        )
+        .respond_matching(
+            "Gradebook",
+            "<ReportPeriod>1</ReportPeriod>",
+            &gradebook(
+                "Quarter 2",
+                &[
+                    ("1", "Algebra II", "Lee, Dana", "85"),
+                    ("2", "Chemistry", "Ortiz, Sam", "84"),
+                    ("3", "Spanish 2", "Diaz, Ana", "87"),
+                ],
+            ),
+        )
+        .respond_matching(
+            "Gradebook",
+            "<ReportPeriod>2</ReportPeriod>",
+            &gradebook(
+                "Quarter 3",
+                &[
+                    ("1", "Algebra II", "Lee, Dana", "79"),
+                    ("2", "Chemistry", "Ortiz, Sam", ""),
+                    ("3", "Spanish 2", "Diaz, Ana", "90"),
+                ],
+            ),
+        );
+    let client = portal.start().await;
+
+    let trends = client
+        .get_grade_trends(&GradeTrendAnalysis::default())
+        .await
+        .unwrap();
+
+    assert_eq!(trends.periods, ["Quarter 1", "Quarter 2", "Quarter 3"]);
+    assert_eq!(trends.courses.len(), 3);
+
+    let algebra = &trends.courses[0];
+    assert_eq!(algebra.change, Some(79.0 - 92.5));
+    assert_eq!(algebra.drops.len(), 2);
+    assert_eq!(algebra.drops[0].from_period, "Quarter 1");
+    assert_eq!(algebra.drops[0].change(), -7.5);
+
+    // Renamed between quarters, but the same period and teacher.
+    let spanish = &trends.courses[2];
+    assert_eq!(spanish.title, "Spanish II");
+    assert_eq!(spanish.points[2].score, Some(90.0));
+
+    let chemistry = &trends.courses[1];
+    assert_eq!(chemistry.points[2].score, None);
+    assert_eq!(chemistry.change, Some(6.0));
+
+    let improved: Vec<&str> = trends.improved().iter().map(|c| c.title.as_str()).collect();
+    assert_eq!(improved, ["Chemistry", "Spanish II"]);
+    let declined: Vec<&str> = trends.declined().iter().map(|c| c.title.as_str()).collect();
+    assert_eq!(declined, ["Algebra II"]);
+    assert_eq!(trends.with_drops().count(), 1);
+}
+
+#[tokio::test]
+async fn test_get_all_gradebooks_limits_requests_in_flight() {
+    let periods: String = (0..8)
+        .map(|index| {
+            format!(r#"<ReportPeriod Index="{index}" GradePeriod="Week {index}" StartDate="" EndDate="" />"#)
+        })
+        .collect();
+    let portal = MockPortal::new().with_chunk_size(64);
+    portal.respond(
+        "Gradebook",
+        &format!("<Gradebook><ReportingPeriods>{periods}</ReportingPeriods><Courses></Courses></Gradebook>"),
+    );
+    let client = portal.start().await;
+
+    let gradebooks = client.get_all_gradebooks().await.unwrap();
+
+    assert_eq!(gradebooks.len(), 8);
+    assert_eq!(portal.requests_for("Gradebook").len(), 9);
+    assert!(portal.max_in_flight() <= 4);
+}
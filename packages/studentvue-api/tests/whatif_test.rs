@@ -0,0 +1,107 @@
+mod common;
+
+use common::{assignment, mark};
+use studenvue::models::{Assignment, GradeCategory, Mark};
+use studenvue::whatif::GradeCutoffs;
+use studenvue::GradeSimulator;
+
+fn category(name: &str, weight: f64) -> GradeCategory {
+    GradeCategory {
+        name: name.to_string(),
+        weight: Some(weight),
+        points: None,
+        points_possible: None,
+        weighted_percent: None,
+        calculated_mark: String::new(),
+    }
+}
+
+fn semester(categories: Vec<GradeCategory>) -> Mark {
+    Mark {
+        categories,
+        ..mark(
+            "Semester 1",
+            "",
+            vec![
+                Assignment {
+                    assignment_type: "Labs".to_string(),
+                    ..assignment("", "Lab 1", "", "", "18.00 / 20.0000")
+                },
+                Assignment {
+                    assignment_type: "Labs".to_string(),
+                    ..assignment("", "Lab 2", "", "", "14.00 / 20.0000")
+                },
+                Assignment {
+                    assignment_type: "Tests".to_string(),
+                    ..assignment("", "Unit Test", "", "", "40.00 / 50.0000")
+                },
+                Assignment {
+                    assignment_type: "Tests".to_string(),
+                    ..assignment("", "Final", "", "", "50 Points Possible")
+                },
+            ],
+        )
+    }
+}
+
+#[test]
+fn test_assignment_points_parsing() {
+    let lab = assignment("", "Lab 1", "", "", "18.00 / 20.0000");
+    assert_eq!(lab.points_earned(), Some(18.0));
+    assert_eq!(lab.points_possible(), Some(20.0));
+
+    let final_exam = assignment("", "Final", "", "", "50 Points Possible");
+    assert!(!final_exam.is_graded());
+    assert_eq!(final_exam.points_possible(), Some(50.0));
+
+    let mut scored = assignment("", "Quiz", "", "", "");
+    scored.score = "7 out of 10.0000".to_string();
+    assert_eq!(scored.points_earned(), Some(7.0));
+}
+
+#[test]
+fn test_weighted_what_if_and_score_needed() {
+    let mark = semester(vec![
+        category("Labs", 40.0),
+        category("Tests", 60.0),
+        category("TOTAL", 100.0),
+    ]);
+    let mut simulator = GradeSimulator::new(&mark);
+
+    // Labs 80%, tests 80%.
+    let grade = simulator.grade();
+    assert!((grade.percent.unwrap() - 80.0).abs() < 1e-9);
+    assert_eq!(grade.letter.as_deref(), Some("B-"));
+
+    assert!(simulator.set_score("final", 45.0, 50.0));
+    let grade = simulator.grade();
+    // Tests become 85/100.
+    assert!((grade.percent.unwrap() - (0.4 * 80.0 + 0.6 * 85.0)).abs() < 1e-9);
+    assert_eq!(grade.letter.as_deref(), Some("B"));
+
+    let needed = simulator.score_needed("Final", "B+").unwrap();
+    // 0.4 * 80 + 0.6 * (40 + x) = 87  =>  x = 51.67, beyond the 50 possible.
+    assert!((needed.points - (55.0 / 0.6 - 40.0)).abs() < 1e-6);
+    assert!(!needed.achievable);
+
+    let needed = simulator.score_needed("Final", "B").unwrap();
+    assert!(needed.achievable);
+    assert!(simulator.score_needed("Final", "Z").is_none());
+    assert!(!simulator.set_score("Essay", 1.0, 1.0));
+}
+
+#[test]
+fn test_total_points_what_if_with_new_assignment() {
+    let mut simulator = GradeSimulator::new(&semester(Vec::new()))
+        .with_cutoffs(GradeCutoffs::new(&[("Pass", 70.0), ("Fail", 0.0)]));
+
+    // 72 of 90 points.
+    assert!((simulator.grade().percent.unwrap() - 80.0).abs() < 1e-9);
+
+    simulator.add_assignment("Project", "Projects", 0.0, 100.0);
+    assert_eq!(simulator.grade().letter.as_deref(), Some("Fail"));
+
+    let needed = simulator.score_needed("Project", "Pass").unwrap();
+    assert!((needed.points - (0.7 * 190.0 - 72.0)).abs() < 1e-9);
+    assert!(needed.achievable);
+}